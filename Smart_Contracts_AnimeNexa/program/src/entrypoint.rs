@@ -0,0 +1,22 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint, entrypoint::ProgramResult,
+    program_error::PrintProgramError, pubkey::Pubkey,
+};
+
+use crate::{error::AnimeNexaError, processor};
+
+// Smart contract entrypoint
+entrypoint!(process_instruction);
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    if let Err(error) = processor::process_instruction(program_id, accounts, instruction_data) {
+        // Log the readable form of custom errors before returning the code
+        error.print::<AnimeNexaError>();
+        return Err(error);
+    }
+    Ok(())
+}
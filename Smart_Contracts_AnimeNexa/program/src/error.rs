@@ -0,0 +1,152 @@
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use solana_program::{
+    decode_error::DecodeError,
+    msg,
+    program_error::{PrintProgramError, ProgramError},
+};
+use thiserror::Error;
+
+// Errors returned by the program, surfaced to clients as `ProgramError::Custom`
+//
+// The discriminant of each variant is its custom error code, so new variants
+// must only ever be appended.
+#[derive(Clone, Copy, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum AnimeNexaError {
+    #[error("Username is already registered")]
+    UsernameTaken,
+    #[error("Username is empty, too long or contains invalid characters")]
+    InvalidUsername,
+    #[error("Wallet already has a registered user")]
+    UserAlreadyRegistered,
+    #[error("User ID does not match the user account")]
+    UserIdMismatch,
+    #[error("Public key does not match the user account")]
+    PublicKeyMismatch,
+    #[error("User is not the creator of the content")]
+    NotCreator,
+    #[error("User is not a member of the group")]
+    NotGroupMember,
+    #[error("Amount does not match the content price")]
+    PriceMismatch,
+    #[error("Content ID does not match the content account")]
+    ContentIdMismatch,
+    #[error("Signer is not authorized for this account")]
+    Unauthorized,
+    #[error("ID is empty or too long")]
+    InvalidId,
+    #[error("Wallet does not belong to the user")]
+    WalletMismatch,
+    #[error("Token account is not the expected associated token account")]
+    TokenAccountMismatch,
+    #[error("Mint does not match the content's payment mint")]
+    PaymentMintMismatch,
+    #[error("Basis points exceed 100%")]
+    InvalidBasisPoints,
+    #[error("Revenue shares must be at most the maximum count and add up to 100%")]
+    InvalidRevenueShares,
+    #[error("Recipient accounts do not match the content's revenue shares")]
+    RevenueShareMismatch,
+    #[error("Text exceeds the maximum length")]
+    TextTooLong,
+    #[error("Amount must be greater than zero")]
+    InvalidAmount,
+    #[error("Content has already been purchased by this user")]
+    AlreadyPurchased,
+    #[error("Payment mint cannot change after the first sale")]
+    PaymentMintLocked,
+    #[error("Manga ID does not match the manga account")]
+    MangaIdMismatch,
+    #[error("Manga is not free and has not been purchased")]
+    MangaNotPurchased,
+    #[error("Access terms are invalid")]
+    InvalidAccessTerms,
+    #[error("Manga access has expired")]
+    AccessExpired,
+    #[error("Manga access does not cover this chapter")]
+    ChapterNotCovered,
+    #[error("Chapter has too many pages")]
+    TooManyPages,
+    #[error("Chapter is free or only sold with the whole series")]
+    ChapterNotForSale,
+    #[error("Media reference is missing its size, MIME type or a valid locator")]
+    InvalidMediaReference,
+    #[error("MIME type is not supported for this media type")]
+    UnsupportedMediaType,
+    #[error("Media exceeds the size or duration limit for its type")]
+    MediaTooLarge,
+    #[error("Post ID does not match the post account")]
+    PostIdMismatch,
+    #[error("Post already has the maximum number of media attached")]
+    TooManyMedia,
+    #[error("Post content has too many parts or an invalid mention or hashtag")]
+    InvalidPostContent,
+    #[error("Post has been deleted")]
+    PostDeleted,
+    #[error("Parent comment does not belong to the post")]
+    CommentNotInThread,
+    #[error("Comment has been deleted")]
+    CommentDeleted,
+    #[error("Comment ID does not match the comment account")]
+    CommentIdMismatch,
+    #[error("Reaction does not belong to the target")]
+    ReactionMismatch,
+    #[error("Users cannot follow themselves")]
+    CannotFollowSelf,
+    #[error("User is already followed")]
+    AlreadyFollowing,
+    #[error("User is not followed")]
+    NotFollowing,
+    #[error("Mentioned username is not registered")]
+    UnknownMention,
+    #[error("Group has the maximum number of members")]
+    GroupFull,
+    #[error("User is already a member of the group")]
+    AlreadyGroupMember,
+    #[error("Member's role does not allow this action")]
+    InsufficientRole,
+    #[error("Group owner must transfer ownership before leaving")]
+    OwnerCannotLeave,
+    #[error("Group ID does not match the group account")]
+    GroupIdMismatch,
+    #[error("Users cannot tip themselves")]
+    CannotTipSelf,
+    #[error("Chapters are numbered from 1")]
+    InvalidChapterNumber,
+}
+
+impl AnimeNexaError {
+    /// Turn a `ProgramError::Custom` code back into the error it came from.
+    pub fn from_code(code: u32) -> Option<Self> {
+        Self::from_u32(code)
+    }
+
+    /// Decode a program error returned by the program, if it is one of ours.
+    pub fn from_program_error(error: &ProgramError) -> Option<Self> {
+        match error {
+            ProgramError::Custom(code) => Self::from_code(*code),
+            _ => None,
+        }
+    }
+}
+
+impl From<AnimeNexaError> for ProgramError {
+    fn from(e: AnimeNexaError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl<T> DecodeError<T> for AnimeNexaError {
+    fn type_of() -> &'static str {
+        "AnimeNexaError"
+    }
+}
+
+impl PrintProgramError for AnimeNexaError {
+    fn print<E>(&self)
+    where
+        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
+    {
+        msg!("Error: {}", self);
+    }
+}
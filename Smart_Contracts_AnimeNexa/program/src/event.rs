@@ -0,0 +1,32 @@
+//! Structured events logged by the program for off-chain indexers.
+//!
+//! Each event is borsh-encoded and written with `sol_log_data`, so it shows up
+//! as a `Program data:` line in the transaction logs and can be decoded with
+//! [`PostEvent::try_from_slice`](borsh::BorshDeserialize::try_from_slice).
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{log::sol_log_data, program_error::ProgramError, pubkey::Pubkey};
+
+/// Something a post refers to, logged when the post's content is published.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum PostEvent {
+    Hashtag {
+        post: Pubkey,   // Post account
+        author: Pubkey, // Author's user account
+        hashtag: String,
+    },
+    Mention {
+        post: Pubkey,
+        author: Pubkey,
+        mentioned_user: Pubkey, // Mentioned user's account, from the username registry
+        username: String,       // Normalized username
+    },
+}
+
+impl PostEvent {
+    /// Write the event to the transaction logs.
+    pub fn emit(&self) -> Result<(), ProgramError> {
+        sol_log_data(&[&borsh::to_vec(self)?]);
+        Ok(())
+    }
+}
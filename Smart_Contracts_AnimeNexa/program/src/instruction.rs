@@ -0,0 +1,302 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::state::{
+    AccessTerms, GroupRole, MediaReference, MediaType, PostContent, PostVisibility, ReactionKind,
+    ReactionTarget, RevenueShare,
+};
+
+// Enum to handle different instructions
+//
+// This is the only instruction enum understood by the program. New variants
+// must be appended so that the borsh discriminant of existing ones never moves.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum UserInstruction {
+    // Accounts: [signer, writable] wallet, [writable] user PDA, [writable] username PDA, [] system program
+    RegisterUser {
+        user_id: String,
+        username: String,
+        profile_data: String,
+    },
+    // Accounts: [] user
+    AuthenticateUser {
+        user_id: String,
+        public_key: Pubkey,
+    },
+    // Accounts: [] user, [signer] wallet, [writable] post PDA, [signer, writable] payer, [] system program,
+    //           then one [] username registry PDA per mention, in mention order
+    //
+    // Logs a `PostEvent` for every hashtag and mention of the content.
+    CreatePost {
+        post_id: String,
+        user_id: String,
+        content: PostContent,
+        timestamp: u64,
+        visibility: PostVisibility,
+    },
+    // Accounts: [] user, [signer] wallet, [writable] post, [writable] media PDA, [signer, writable] payer,
+    //           [] system program
+    //
+    // The post must be one of the user's own; the media is added to its attachments.
+    UploadMedia {
+        media_id: String,
+        post_id: String,
+        user_id: String,
+        reference: MediaReference,
+        media_type: MediaType,
+    },
+    // Accounts: [] sender user, [signer, writable] sender wallet, [writable] recipient user,
+    //           [writable] recipient wallet, [writable] support PDA, [] system program
+    SupportUser {
+        support_id: String,
+        sender_user_id: String,
+        recipient_user_id: String,
+        amount: u64,
+        timestamp: u64,
+        message: Option<String>,
+    },
+    // Accounts: [] creator, [signer, writable] wallet, [writable] content PDA, [] system program
+    //
+    // Creates the content on first use and updates its price afterwards.
+    // `payment_mint` is None to price in lamports, or the SPL token mint to be paid in.
+    // `access_terms` decide what a purchase grants when the content sells a manga.
+    MonetizeContent {
+        content_id: String,
+        creator_user_id: String,
+        price: u64,
+        payment_mint: Option<Pubkey>,
+        access_terms: AccessTerms,
+    },
+    // Accounts: [writable] creator wallet, [signer, writable] purchaser wallet, [writable] content,
+    //           [] purchaser user, [] creator user, [] system program, [] config PDA, [writable] treasury,
+    //           [writable] receipt PDA
+    //           For token-priced content additionally: [writable] purchaser token account,
+    //           [writable] creator token account, [] mint, [] token program
+    //           Then one [writable] recipient per revenue share, in table order
+    //
+    // The treasury, creator and recipients are wallets for SOL-priced content and
+    // their associated token accounts for token-priced content.
+    PurchaseContent {
+        content_id: String,
+        purchaser_user_id: String,
+        amount: u64,
+        timestamp: u64,
+    },
+    // Accounts: [] creator, [signer] wallet, [writable] manga PDA, [signer, writable] payer, [] system program
+    //
+    // A manga is sold by monetizing content with the same ID as the manga.
+    UploadManga {
+        manga_id: String,
+        creator_user_id: String,
+        title: String,
+        description: String,
+        timestamp: u64,
+        is_free: bool,
+    },
+    // Accounts: [] user, [signer, writable] wallet, [writable] manga access PDA, [] manga, [] creator user,
+    //           [] series receipt PDA, [] system program,
+    //           [] chapter receipt PDA (only when `chapter` is given)
+    //
+    // Either receipt may be uninitialized when the other one, the free preview or
+    // ownership of the manga grants access. With `chapter`, the series purchase,
+    // the free preview or the chapter's own receipt must cover that chapter;
+    // without it, only a live series purchase or the free preview counts. The
+    // access record keeps the grant that was used.
+    AccessManga {
+        manga_id: String,
+        user_id: String,
+        chapter: Option<u32>,
+    },
+    // Accounts: [] sender, [signer, writable] wallet, [] recipient, [writable] message PDA, [] system program
+    SendMessage {
+        message_id: String,
+        sender_user_id: String,
+        recipient_user_id: String,
+        message_content: String,
+    },
+    // Accounts: [] user, [signer, writable] wallet, [writable] group PDA, [] system program,
+    //           then one [] user account per initial member
+    //
+    // The user becomes the group's owner. Fails if the group already exists.
+    CreateGroup {
+        group_id: String,
+    },
+    // Accounts: [] user, [signer, writable] wallet, [] group, [writable] group message PDA,
+    //           [] system program
+    SendGroupMessage {
+        message_id: String,
+        group_id: String,
+        user_id: String,
+        message_content: String,
+    },
+    // Accounts: [signer, writable] upgrade authority, [writable] config PDA, [] program data, [] system program
+    InitializeConfig {
+        fee_basis_points: u16,
+        treasury: Pubkey,
+    },
+    // Accounts: [signer] admin, [writable] config PDA
+    UpdateConfig {
+        fee_basis_points: u16,
+        treasury: Pubkey,
+    },
+    // Accounts: [] creator, [signer, writable] wallet, [writable] content PDA, [] system program
+    SetRevenueShares {
+        content_id: String,
+        revenue_shares: Vec<RevenueShare>,
+    },
+    // Accounts: [] user, [] manga, [] creator user, [] series receipt PDA,
+    //           [] chapter receipt PDA (only when `chapter` is given)
+    //
+    // Read-only: resolves access from the receipts as AccessManga does, and
+    // succeeds when a live grant covers the manga, or `chapter` if given.
+    CheckMangaAccess {
+        manga_id: String,
+        user_id: String,
+        chapter: Option<u32>,
+    },
+    // Accounts: [] creator, [signer, writable] wallet, [writable] manga, [writable] chapter PDA, [] system program
+    //
+    // Chapters are numbered from 1, so chapters 1..=free_chapters form the free preview.
+    // The chapter's content hash is computed from `pages`.
+    AddChapter {
+        manga_id: String,
+        chapter_number: u32,
+        title: String,
+        pages: Vec<MediaReference>,
+        price: u64,
+    },
+    // Accounts: [] creator, [signer, writable] wallet, [writable] manga, [writable] chapter PDA, [] system program
+    UpdateChapter {
+        manga_id: String,
+        chapter_number: u32,
+        title: String,
+        pages: Vec<MediaReference>,
+        price: u64,
+    },
+    // Accounts: [] creator, [signer, writable] wallet, [writable] manga, [writable] chapter PDA
+    //
+    // The chapter's rent is refunded to the wallet.
+    RemoveChapter {
+        manga_id: String,
+        chapter_number: u32,
+    },
+    // Accounts: [] creator, [signer] wallet, [writable] manga
+    SetFreeChapters {
+        manga_id: String,
+        free_chapters: u32,
+    },
+    // Accounts: same as PurchaseContent, with the series content PDA and a receipt PDA
+    //           derived from the chapter account, followed by [] manga, [] chapter,
+    //           [] series receipt PDA ahead of the token accounts and revenue share
+    //           recipients
+    //
+    // The series receipt may be uninitialized; fails if it covers the chapter.
+    PurchaseChapter {
+        manga_id: String,
+        chapter_number: u32,
+        purchaser_user_id: String,
+        amount: u64,
+        timestamp: u64,
+    },
+    // Accounts: [] user, [signer, writable] wallet, [writable] post, [] system program,
+    //           then one [] username registry PDA per mention, in mention order
+    EditPost {
+        post_id: String,
+        content: PostContent,
+    },
+    // Accounts: [] user, [signer, writable] wallet, [writable] post, [] system program,
+    //           [writable] original post and [writable] repost record PDA (only when deleting a repost),
+    //           then one [writable] media PDA per attached media, in `media_ids` order
+    //
+    // The post is kept as a tombstone and its media are closed; the rent they no
+    // longer need goes to the wallet.
+    DeletePost {
+        post_id: String,
+    },
+    // Accounts: [] user, [signer, writable] wallet, [writable] post, [] post author user,
+    //           [writable] comment PDA, [] system program,
+    //           [writable] parent comment (only when `parent_comment` is given)
+    CreateComment {
+        comment_id: String,
+        user_id: String,
+        text: String,
+        parent_comment: Option<Pubkey>,
+    },
+    // Accounts: [] user, [signer, writable] wallet, [writable] comment, [writable] post, [] system program,
+    //           [writable] parent comment (only when the comment is a reply)
+    //
+    // The comment is kept as a tombstone so replies keep their parent; the rent it
+    // no longer needs goes to the wallet.
+    DeleteComment {
+        comment_id: String,
+    },
+    // Accounts: [] user, [signer, writable] wallet, [writable] target post, manga or comment,
+    //           [] target's author or creator user (unused for comments), [writable] reaction PDA,
+    //           [] system program
+    //
+    // Reacting again to the same target switches the kind of the existing reaction.
+    React {
+        user_id: String,
+        target: ReactionTarget,
+        kind: ReactionKind,
+    },
+    // Accounts: [] user, [signer, writable] wallet, [writable] target, [] target's author or creator user,
+    //           [writable] reaction PDA
+    //
+    // The reaction's rent is refunded to the wallet.
+    Unreact,
+    // Accounts: [writable] follower user, [signer, writable] wallet, [writable] followee user,
+    //           [writable] follow PDA, [] system program
+    Follow,
+    // Accounts: [writable] follower user, [signer, writable] wallet, [writable] followee user,
+    //           [writable] follow PDA
+    //
+    // The follow edge's rent is refunded to the wallet.
+    Unfollow,
+    // Accounts: [] user, [signer, writable] wallet, [writable] repost PDA, [writable] original post,
+    //           [] original author user, [writable] repost record PDA, [] system program,
+    //           then one [] username registry PDA per mention of the quote, in mention order
+    //
+    // Creates a post of the user's own referring to the original; `quote` adds commentary.
+    // The original counts each reposting user once, however many reposts they make, and
+    // a repost is never more visible than the original.
+    Repost {
+        post_id: String,
+        user_id: String,
+        quote: Option<PostContent>,
+        timestamp: u64,
+        visibility: PostVisibility,
+    },
+    // Accounts: [] user, [signer, writable] wallet, [writable] group, [] new member user, [] system program
+    //
+    // Requires a moderator or higher; the new member joins with the member role.
+    AddMember {
+        group_id: String,
+    },
+    // Accounts: [] user, [signer] wallet, [writable] group, [] member user
+    //
+    // Requires a role above the removed member's. The rent freed by the smaller
+    // group stays in the group account.
+    RemoveMember {
+        group_id: String,
+    },
+    // Accounts: [] user, [signer] wallet, [writable] group
+    //
+    // The rent freed by the smaller group stays in the group account.
+    LeaveGroup {
+        group_id: String,
+    },
+    // Accounts: [] owner user, [signer] wallet, [writable] group, [] new owner user
+    //
+    // The new owner must already be a member; the previous owner becomes an admin.
+    TransferOwnership {
+        group_id: String,
+    },
+    // Accounts: [] user, [signer] wallet, [writable] group, [] member user
+    //
+    // Requires an admin or the owner, outranking both the member's current and new role.
+    SetRole {
+        group_id: String,
+        role: GroupRole,
+    },
+}
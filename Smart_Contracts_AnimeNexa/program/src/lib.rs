@@ -0,0 +1,17 @@
+//! The AnimeNexa on-chain program.
+//!
+//! All instructions share the single [`instruction::UserInstruction`] enum and
+//! are dispatched from one entrypoint, so a serialized instruction means the
+//! same thing regardless of which client built it.
+
+#[cfg(not(feature = "no-entrypoint"))]
+mod entrypoint;
+pub mod error;
+pub mod event;
+pub mod instruction;
+pub mod payment;
+pub mod pda;
+pub mod processor;
+pub mod utils;
+
+pub use animenexa_state as state;
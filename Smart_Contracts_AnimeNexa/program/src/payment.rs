@@ -0,0 +1,185 @@
+//! Moving funds from a buyer or supporter to the accounts being paid.
+//!
+//! Content can be priced in SOL or in any SPL token; payments in a token move
+//! between the associated token accounts of the payer and payee wallets.
+
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program::invoke,
+    program_error::ProgramError, program_pack::Pack, pubkey::Pubkey, system_instruction,
+    system_program,
+};
+
+use crate::{
+    error::AnimeNexaError,
+    pda::find_associated_token_address,
+    state::{config::BASIS_POINTS_DENOMINATOR, RevenueShare},
+};
+
+/// How a payment is settled, resolved once per instruction from its accounts.
+pub enum PaymentRoute<'a, 'b> {
+    // Lamports move straight between wallets
+    Sol {
+        system_program: &'b AccountInfo<'a>,
+    },
+    // Tokens move out of the payer's associated token account for `mint`
+    Token {
+        source: &'b AccountInfo<'a>,
+        mint: &'b AccountInfo<'a>,
+        token_program: &'b AccountInfo<'a>,
+    },
+}
+
+impl<'a, 'b> PaymentRoute<'a, 'b> {
+    /// Pay `amount` from `payer` to `recipient_wallet`. `destination` is the
+    /// wallet itself for SOL, or its associated token account for tokens.
+    pub fn pay(
+        &self,
+        payer: &AccountInfo<'a>,
+        destination: &AccountInfo<'a>,
+        recipient_wallet: &Pubkey,
+        amount: u64,
+    ) -> ProgramResult {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        match self {
+            PaymentRoute::Sol { system_program } => {
+                if destination.key != recipient_wallet {
+                    msg!("Destination is not the payee's wallet!");
+                    return Err(AnimeNexaError::WalletMismatch.into());
+                }
+                transfer_sol(payer, destination, system_program, amount)
+            }
+            PaymentRoute::Token {
+                source,
+                mint,
+                token_program,
+            } => transfer_token(
+                source,
+                destination,
+                mint,
+                payer,
+                recipient_wallet,
+                token_program,
+                amount,
+            ),
+        }
+    }
+}
+
+/// Split of a purchase between the platform, contributors and the creator.
+#[derive(Debug, PartialEq)]
+pub struct Split {
+    pub platform_fee: u64,
+    pub share_amounts: Vec<u64>, // One entry per revenue share, in table order
+    pub creator_amount: u64,     // Everything not taken by the fee or the shares
+}
+
+/// Divide `amount` by taking the platform fee first and splitting the rest
+/// according to `shares`. Rounding dust always goes to the creator.
+pub fn split_amount(
+    amount: u64,
+    fee_basis_points: u16,
+    shares: &[RevenueShare],
+) -> Result<Split, ProgramError> {
+    let platform_fee = basis_points_of(amount, fee_basis_points)?;
+    let net = amount
+        .checked_sub(platform_fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let share_amounts = shares
+        .iter()
+        .map(|share| basis_points_of(net, share.basis_points))
+        .collect::<Result<Vec<_>, _>>()?;
+    let shared = share_amounts
+        .iter()
+        .try_fold(0u64, |total, amount| total.checked_add(*amount))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let creator_amount = net
+        .checked_sub(shared)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(Split {
+        platform_fee,
+        share_amounts,
+        creator_amount,
+    })
+}
+
+fn basis_points_of(amount: u64, basis_points: u16) -> Result<u64, ProgramError> {
+    let value = (amount as u128)
+        .checked_mul(basis_points as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        / BASIS_POINTS_DENOMINATOR as u128;
+    u64::try_from(value).map_err(|_| ProgramError::ArithmeticOverflow)
+}
+
+// Transfer lamports from a signing wallet through the system program
+pub fn transfer_sol<'a>(
+    from: &AccountInfo<'a>,
+    to: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    if *system_program.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if from.lamports().checked_sub(amount).is_none() {
+        msg!("Insufficient funds for payment!");
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    invoke(
+        &system_instruction::transfer(from.key, to.key, amount),
+        &[from.clone(), to.clone(), system_program.clone()],
+    )
+}
+
+/// Transfer `amount` of `mint` from the associated token account of
+/// `authority` to the associated token account of `recipient_wallet`.
+pub fn transfer_token<'a>(
+    source: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    recipient_wallet: &Pubkey,
+    token_program: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    if *token_program.key != spl_token::id() || *mint.owner != spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if *source.key != find_associated_token_address(authority.key, mint.key).0 {
+        msg!("Source is not the payer's associated token account!");
+        return Err(AnimeNexaError::TokenAccountMismatch.into());
+    }
+    if *destination.key != find_associated_token_address(recipient_wallet, mint.key).0 {
+        msg!("Destination is not the payee's associated token account!");
+        return Err(AnimeNexaError::TokenAccountMismatch.into());
+    }
+
+    let decimals = spl_token::state::Mint::unpack(&mint.data.borrow())?.decimals;
+
+    invoke(
+        &spl_token::instruction::transfer_checked(
+            token_program.key,
+            source.key,
+            mint.key,
+            destination.key,
+            authority.key,
+            &[],
+            amount,
+            decimals,
+        )?,
+        &[
+            source.clone(),
+            mint.clone(),
+            destination.clone(),
+            authority.clone(),
+            token_program.clone(),
+        ],
+    )
+}
@@ -0,0 +1,290 @@
+//! Program-derived addresses used by the program.
+//!
+//! Clients should derive account addresses with these helpers rather than
+//! re-implementing the seed layout.
+
+use solana_program::{pubkey, pubkey::Pubkey};
+
+/// The SPL associated token account program.
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const USER_SEED: &[u8] = b"user";
+pub const USERNAME_SEED: &[u8] = b"username";
+pub const POST_SEED: &[u8] = b"post";
+pub const REPOST_SEED: &[u8] = b"repost";
+pub const COMMENT_SEED: &[u8] = b"comment";
+pub const MEDIA_SEED: &[u8] = b"media";
+pub const FOLLOW_SEED: &[u8] = b"follow";
+pub const GROUP_SEED: &[u8] = b"group";
+pub const GROUP_MESSAGE_SEED: &[u8] = b"group_message";
+pub const MESSAGE_SEED: &[u8] = b"message";
+pub const MANGA_SEED: &[u8] = b"manga";
+pub const CHAPTER_SEED: &[u8] = b"chapter";
+pub const MANGA_ACCESS_SEED: &[u8] = b"manga_access";
+pub const SUPPORT_SEED: &[u8] = b"support";
+pub const CONTENT_SEED: &[u8] = b"content";
+pub const REACTION_SEED: &[u8] = b"reaction";
+pub const RECEIPT_SEED: &[u8] = b"receipt";
+
+/// Longest record ID (post, media, manga, ...) that can be used as a seed.
+pub const MAX_ID_LEN: usize = 32;
+
+/// Longest username accepted, bounded by the 32-byte limit on a single seed.
+pub const MAX_USERNAME_LEN: usize = 32;
+
+/// Canonical form of a username, used both as the registry seed and for
+/// uniqueness comparisons. Names differing only by case or surrounding
+/// whitespace map to the same registry entry.
+pub fn normalize_username(username: &str) -> String {
+    username.trim().to_ascii_lowercase()
+}
+
+/// Whether a normalized username is allowed: ASCII letters, digits and
+/// underscores, short enough to be a seed.
+pub fn is_valid_username(normalized: &str) -> bool {
+    !normalized.is_empty()
+        && normalized.len() <= MAX_USERNAME_LEN
+        && normalized
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_')
+}
+
+// Derive the global platform config
+pub fn find_config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], program_id)
+}
+
+// Derive the user account owned by `wallet`
+pub fn find_user_address(program_id: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[USER_SEED, wallet.as_ref()], program_id)
+}
+
+// Derive the registry entry reserving `username`
+pub fn find_username_address(program_id: &Pubkey, username: &str) -> (Pubkey, u8) {
+    let normalized = normalize_username(username);
+    Pubkey::find_program_address(&[USERNAME_SEED, normalized.as_bytes()], program_id)
+}
+
+// Derive the edge of `follower_account` following `followee_account`
+pub fn find_follow_address(
+    program_id: &Pubkey,
+    follower_account: &Pubkey,
+    followee_account: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            FOLLOW_SEED,
+            follower_account.as_ref(),
+            followee_account.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+// Derive the record of `user_account`'s reposts of `original_account`
+pub fn find_repost_address(
+    program_id: &Pubkey,
+    original_account: &Pubkey,
+    user_account: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            REPOST_SEED,
+            original_account.as_ref(),
+            user_account.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+// Derive a group created by `creator_account`
+pub fn find_group_address(
+    program_id: &Pubkey,
+    creator_account: &Pubkey,
+    group_id: &str,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[GROUP_SEED, creator_account.as_ref(), group_id.as_bytes()],
+        program_id,
+    )
+}
+
+// Derive a direct message sent by `sender_account`
+pub fn find_message_address(
+    program_id: &Pubkey,
+    sender_account: &Pubkey,
+    message_id: &str,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MESSAGE_SEED, sender_account.as_ref(), message_id.as_bytes()],
+        program_id,
+    )
+}
+
+// Derive a message sent to `group_account` by `sender_account`
+pub fn find_group_message_address(
+    program_id: &Pubkey,
+    group_account: &Pubkey,
+    sender_account: &Pubkey,
+    message_id: &str,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            GROUP_MESSAGE_SEED,
+            group_account.as_ref(),
+            sender_account.as_ref(),
+            message_id.as_bytes(),
+        ],
+        program_id,
+    )
+}
+
+// Derive a post created by `user_account`
+pub fn find_post_address(
+    program_id: &Pubkey,
+    user_account: &Pubkey,
+    post_id: &str,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[POST_SEED, user_account.as_ref(), post_id.as_bytes()],
+        program_id,
+    )
+}
+
+// Derive a comment written by `user_account`
+pub fn find_comment_address(
+    program_id: &Pubkey,
+    user_account: &Pubkey,
+    comment_id: &str,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[COMMENT_SEED, user_account.as_ref(), comment_id.as_bytes()],
+        program_id,
+    )
+}
+
+// Derive a media record uploaded by `user_account`
+pub fn find_media_address(
+    program_id: &Pubkey,
+    user_account: &Pubkey,
+    media_id: &str,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MEDIA_SEED, user_account.as_ref(), media_id.as_bytes()],
+        program_id,
+    )
+}
+
+// Derive a manga series published by `creator_account`
+pub fn find_manga_address(
+    program_id: &Pubkey,
+    creator_account: &Pubkey,
+    manga_id: &str,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MANGA_SEED, creator_account.as_ref(), manga_id.as_bytes()],
+        program_id,
+    )
+}
+
+// Derive chapter `chapter_number` of `manga_account`
+pub fn find_chapter_address(
+    program_id: &Pubkey,
+    manga_account: &Pubkey,
+    chapter_number: u32,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            CHAPTER_SEED,
+            manga_account.as_ref(),
+            &chapter_number.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+// Derive the access record of `user_account` for `manga_account`
+pub fn find_manga_access_address(
+    program_id: &Pubkey,
+    manga_account: &Pubkey,
+    user_account: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            MANGA_ACCESS_SEED,
+            manga_account.as_ref(),
+            user_account.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+// Derive monetized content owned by `creator_account`
+pub fn find_content_address(
+    program_id: &Pubkey,
+    creator_account: &Pubkey,
+    content_id: &str,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            CONTENT_SEED,
+            creator_account.as_ref(),
+            content_id.as_bytes(),
+        ],
+        program_id,
+    )
+}
+
+// Derive the receipt of `purchaser_account` buying `content_account`
+pub fn find_receipt_address(
+    program_id: &Pubkey,
+    content_account: &Pubkey,
+    purchaser_account: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            RECEIPT_SEED,
+            content_account.as_ref(),
+            purchaser_account.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+// Derive the reaction of `user_account` on `target_account`
+pub fn find_reaction_address(
+    program_id: &Pubkey,
+    target_account: &Pubkey,
+    user_account: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            REACTION_SEED,
+            target_account.as_ref(),
+            user_account.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+// Derive a tip sent by `sender_account`
+pub fn find_support_address(
+    program_id: &Pubkey,
+    sender_account: &Pubkey,
+    support_id: &str,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SUPPORT_SEED, sender_account.as_ref(), support_id.as_bytes()],
+        program_id,
+    )
+}
+
+// Derive the SPL associated token account of `wallet` for `mint`
+pub fn find_associated_token_address(wallet: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), spl_token::id().as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+}
@@ -0,0 +1,274 @@
+use borsh::BorshDeserialize;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+use crate::instruction::UserInstruction;
+
+pub mod chapter;
+pub mod comment;
+pub mod config;
+pub mod follow;
+pub mod group;
+pub mod manga;
+pub mod media;
+pub mod messaging;
+pub mod monetization;
+pub mod post;
+pub mod reaction;
+pub mod support;
+pub mod user;
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = UserInstruction::try_from_slice(instruction_data)?;
+
+    match instruction {
+        UserInstruction::RegisterUser {
+            user_id,
+            username,
+            profile_data,
+        } => user::register_user(program_id, accounts, user_id, username, profile_data),
+        UserInstruction::AuthenticateUser {
+            user_id,
+            public_key,
+        } => user::authenticate_user(program_id, accounts, user_id, public_key),
+        UserInstruction::CreatePost {
+            post_id,
+            user_id,
+            content,
+            timestamp,
+            visibility,
+        } => post::create_post(
+            program_id, accounts, post_id, user_id, content, timestamp, visibility,
+        ),
+        UserInstruction::UploadMedia {
+            media_id,
+            post_id,
+            user_id,
+            reference,
+            media_type,
+        } => media::upload_media(
+            program_id, accounts, media_id, post_id, user_id, reference, media_type,
+        ),
+        UserInstruction::SupportUser {
+            support_id,
+            sender_user_id,
+            recipient_user_id,
+            amount,
+            timestamp,
+            message,
+        } => support::support_user(
+            program_id,
+            accounts,
+            support_id,
+            sender_user_id,
+            recipient_user_id,
+            amount,
+            timestamp,
+            message,
+        ),
+        UserInstruction::MonetizeContent {
+            content_id,
+            creator_user_id,
+            price,
+            payment_mint,
+            access_terms,
+        } => monetization::monetize_content(
+            program_id,
+            accounts,
+            content_id,
+            creator_user_id,
+            price,
+            payment_mint,
+            access_terms,
+        ),
+        UserInstruction::PurchaseContent {
+            content_id,
+            purchaser_user_id,
+            amount,
+            timestamp,
+        } => monetization::purchase_content(
+            program_id,
+            accounts,
+            content_id,
+            purchaser_user_id,
+            amount,
+            timestamp,
+        ),
+        UserInstruction::UploadManga {
+            manga_id,
+            creator_user_id,
+            title,
+            description,
+            timestamp,
+            is_free,
+        } => manga::upload_manga(
+            program_id,
+            accounts,
+            manga_id,
+            creator_user_id,
+            title,
+            description,
+            timestamp,
+            is_free,
+        ),
+        UserInstruction::AccessManga {
+            manga_id,
+            user_id,
+            chapter,
+        } => manga::access_manga(program_id, accounts, manga_id, user_id, chapter),
+        UserInstruction::SendMessage {
+            message_id,
+            sender_user_id,
+            recipient_user_id,
+            message_content,
+        } => messaging::send_message(
+            program_id,
+            accounts,
+            message_id,
+            sender_user_id,
+            recipient_user_id,
+            message_content,
+        ),
+        UserInstruction::CreateGroup { group_id } => {
+            group::create_group(program_id, accounts, group_id)
+        }
+        UserInstruction::SendGroupMessage {
+            message_id,
+            group_id,
+            user_id,
+            message_content,
+        } => group::send_group_message(
+            program_id,
+            accounts,
+            message_id,
+            group_id,
+            user_id,
+            message_content,
+        ),
+        UserInstruction::InitializeConfig {
+            fee_basis_points,
+            treasury,
+        } => config::initialize_config(program_id, accounts, fee_basis_points, treasury),
+        UserInstruction::UpdateConfig {
+            fee_basis_points,
+            treasury,
+        } => config::update_config(program_id, accounts, fee_basis_points, treasury),
+        UserInstruction::SetRevenueShares {
+            content_id,
+            revenue_shares,
+        } => monetization::set_revenue_shares(program_id, accounts, content_id, revenue_shares),
+        UserInstruction::CheckMangaAccess {
+            manga_id,
+            user_id,
+            chapter,
+        } => manga::check_manga_access(program_id, accounts, manga_id, user_id, chapter),
+        UserInstruction::AddChapter {
+            manga_id,
+            chapter_number,
+            title,
+            pages,
+            price,
+        } => chapter::add_chapter(
+            program_id,
+            accounts,
+            manga_id,
+            chapter_number,
+            title,
+            pages,
+            price,
+        ),
+        UserInstruction::UpdateChapter {
+            manga_id,
+            chapter_number,
+            title,
+            pages,
+            price,
+        } => chapter::update_chapter(
+            program_id,
+            accounts,
+            manga_id,
+            chapter_number,
+            title,
+            pages,
+            price,
+        ),
+        UserInstruction::RemoveChapter {
+            manga_id,
+            chapter_number,
+        } => chapter::remove_chapter(program_id, accounts, manga_id, chapter_number),
+        UserInstruction::SetFreeChapters {
+            manga_id,
+            free_chapters,
+        } => chapter::set_free_chapters(program_id, accounts, manga_id, free_chapters),
+        UserInstruction::PurchaseChapter {
+            manga_id,
+            chapter_number,
+            purchaser_user_id,
+            amount,
+            timestamp,
+        } => monetization::purchase_chapter(
+            program_id,
+            accounts,
+            manga_id,
+            chapter_number,
+            purchaser_user_id,
+            amount,
+            timestamp,
+        ),
+        UserInstruction::EditPost { post_id, content } => {
+            post::edit_post(program_id, accounts, post_id, content)
+        }
+        UserInstruction::DeletePost { post_id } => post::delete_post(program_id, accounts, post_id),
+        UserInstruction::CreateComment {
+            comment_id,
+            user_id,
+            text,
+            parent_comment,
+        } => comment::create_comment(
+            program_id,
+            accounts,
+            comment_id,
+            user_id,
+            text,
+            parent_comment,
+        ),
+        UserInstruction::DeleteComment { comment_id } => {
+            comment::delete_comment(program_id, accounts, comment_id)
+        }
+        UserInstruction::React {
+            user_id,
+            target,
+            kind,
+        } => reaction::react(program_id, accounts, user_id, target, kind),
+        UserInstruction::Unreact => reaction::unreact(program_id, accounts),
+        UserInstruction::Follow => follow::follow(program_id, accounts),
+        UserInstruction::Unfollow => follow::unfollow(program_id, accounts),
+        UserInstruction::Repost {
+            post_id,
+            user_id,
+            quote,
+            timestamp,
+            visibility,
+        } => post::repost(
+            program_id, accounts, post_id, user_id, quote, timestamp, visibility,
+        ),
+        UserInstruction::AddMember { group_id } => {
+            group::add_member(program_id, accounts, group_id)
+        }
+        UserInstruction::RemoveMember { group_id } => {
+            group::remove_member(program_id, accounts, group_id)
+        }
+        UserInstruction::LeaveGroup { group_id } => {
+            group::leave_group(program_id, accounts, group_id)
+        }
+        UserInstruction::TransferOwnership { group_id } => {
+            group::transfer_ownership(program_id, accounts, group_id)
+        }
+        UserInstruction::SetRole { group_id, role } => {
+            group::set_role(program_id, accounts, group_id, role)
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    error::AnimeNexaError,
+    pda::{find_chapter_address, CHAPTER_SEED},
+    processor::manga::load_manga,
+    state::{
+        manga::{MAX_CHAPTER_PAGES, MAX_CHAPTER_TITLE_LEN},
+        Chapter, Manga, MediaReference, MediaType,
+    },
+    utils::{
+        authorize_user, check_media, check_new_account, close_account, initialize_pda_account,
+        update_account, write_in_place,
+    },
+};
+
+pub fn add_chapter(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    manga_id: String,
+    chapter_number: u32,
+    title: String,
+    pages: Vec<MediaReference>,
+    price: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let creator_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let manga_account = next_account_info(account_info_iter)?;
+    let chapter_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let mut manga = load_creator_manga(
+        program_id,
+        creator_account,
+        wallet_account,
+        manga_account,
+        &manga_id,
+    )?;
+    check_chapter_number(chapter_number)?;
+    check_chapter_contents(&title, &pages)?;
+
+    check_new_account(program_id, wallet_account, chapter_account, system_program)?;
+
+    let (chapter_address, bump) =
+        find_chapter_address(program_id, manga_account.key, chapter_number);
+    if *chapter_account.key != chapter_address {
+        msg!("Chapter account does not match the chapter number!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // Initialize chapter data
+    let chapter = Chapter {
+        manga_id,
+        chapter_number,
+        title,
+        content_hash: Chapter::hash_pages(&pages),
+        pages,
+        price,
+        published_at: now,
+        updated_at: now,
+    };
+
+    initialize_pda_account(
+        wallet_account,
+        chapter_account,
+        system_program,
+        program_id,
+        &chapter,
+        &[
+            CHAPTER_SEED,
+            manga_account.key.as_ref(),
+            &chapter_number.to_le_bytes(),
+            &[bump],
+        ],
+    )?;
+
+    manga.chapter_count = manga
+        .chapter_count
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    write_in_place(manga_account, &manga)?;
+
+    msg!("Chapter added successfully: {:?}", chapter);
+
+    Ok(())
+}
+
+pub fn update_chapter(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    manga_id: String,
+    chapter_number: u32,
+    title: String,
+    pages: Vec<MediaReference>,
+    price: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let creator_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let manga_account = next_account_info(account_info_iter)?;
+    let chapter_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    load_creator_manga(
+        program_id,
+        creator_account,
+        wallet_account,
+        manga_account,
+        &manga_id,
+    )?;
+    check_chapter_number(chapter_number)?;
+    check_chapter_contents(&title, &pages)?;
+
+    let mut chapter = load_chapter(program_id, manga_account, chapter_account, chapter_number)?;
+
+    chapter.title = title;
+    chapter.content_hash = Chapter::hash_pages(&pages);
+    chapter.pages = pages;
+    chapter.price = price;
+    chapter.updated_at = Clock::get()?.unix_timestamp;
+
+    // Serialize the chapter data back to the account
+    update_account(chapter_account, wallet_account, system_program, &chapter)?;
+
+    msg!("Chapter updated successfully: {:?}", chapter);
+
+    Ok(())
+}
+
+// Remove a chapter, refunding its rent to the creator's wallet
+pub fn remove_chapter(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    manga_id: String,
+    chapter_number: u32,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let creator_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let manga_account = next_account_info(account_info_iter)?;
+    let chapter_account = next_account_info(account_info_iter)?;
+
+    let mut manga = load_creator_manga(
+        program_id,
+        creator_account,
+        wallet_account,
+        manga_account,
+        &manga_id,
+    )?;
+    load_chapter(program_id, manga_account, chapter_account, chapter_number)?;
+
+    close_account(chapter_account, wallet_account)?;
+
+    manga.chapter_count = manga.chapter_count.saturating_sub(1);
+    write_in_place(manga_account, &manga)?;
+
+    msg!(
+        "Chapter {} removed from manga {}",
+        chapter_number,
+        manga.manga_id
+    );
+
+    Ok(())
+}
+
+// Make the first `free_chapters` chapters readable without a purchase
+pub fn set_free_chapters(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    manga_id: String,
+    free_chapters: u32,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let creator_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let manga_account = next_account_info(account_info_iter)?;
+
+    let mut manga = load_creator_manga(
+        program_id,
+        creator_account,
+        wallet_account,
+        manga_account,
+        &manga_id,
+    )?;
+
+    manga.free_chapters = free_chapters;
+    write_in_place(manga_account, &manga)?;
+
+    msg!("Free chapters updated successfully: {:?}", manga);
+
+    Ok(())
+}
+
+// Authorize the creator and load the manga they are editing
+fn load_creator_manga(
+    program_id: &Pubkey,
+    creator_account: &AccountInfo,
+    wallet_account: &AccountInfo,
+    manga_account: &AccountInfo,
+    manga_id: &str,
+) -> Result<Manga, ProgramError> {
+    authorize_user(program_id, creator_account, wallet_account)?;
+
+    // The manga PDA is derived from the creator's user account
+    let manga = load_manga(program_id, creator_account, manga_account)?;
+    if manga.manga_id != manga_id {
+        msg!("Manga ID does not match!");
+        return Err(AnimeNexaError::MangaIdMismatch.into());
+    }
+
+    Ok(manga)
+}
+
+// Chapter 0 would sit outside the free preview `1..=free_chapters` while still
+// being refused for sale as one of the free chapters
+fn check_chapter_number(chapter_number: u32) -> ProgramResult {
+    if chapter_number == 0 {
+        msg!("Chapters are numbered from 1!");
+        return Err(AnimeNexaError::InvalidChapterNumber.into());
+    }
+    Ok(())
+}
+
+fn check_chapter_contents(title: &str, pages: &[MediaReference]) -> ProgramResult {
+    if title.len() > MAX_CHAPTER_TITLE_LEN {
+        msg!("Chapter title is too long!");
+        return Err(AnimeNexaError::TextTooLong.into());
+    }
+
+    if pages.len() > MAX_CHAPTER_PAGES {
+        msg!("Chapter has too many pages!");
+        return Err(AnimeNexaError::TooManyPages.into());
+    }
+
+    pages
+        .iter()
+        .try_for_each(|page| check_media(page, MediaType::MangaPage))
+}
+
+/// Load a chapter, checking it is the PDA for `chapter_number` of `manga_account`.
+pub fn load_chapter(
+    program_id: &Pubkey,
+    manga_account: &AccountInfo,
+    chapter_account: &AccountInfo,
+    chapter_number: u32,
+) -> Result<Chapter, ProgramError> {
+    if chapter_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let (chapter_address, _) = find_chapter_address(program_id, manga_account.key, chapter_number);
+    if *chapter_account.key != chapter_address {
+        msg!("Chapter account does not match the chapter number!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(Chapter::try_from_slice(&chapter_account.data.borrow())?)
+}
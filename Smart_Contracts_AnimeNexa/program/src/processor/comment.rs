@@ -0,0 +1,211 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    error::AnimeNexaError,
+    pda::{find_comment_address, COMMENT_SEED},
+    processor::post::load_post,
+    state::{comment::MAX_COMMENT_LEN, Comment, Post, ReactionCounts},
+    utils::{
+        authorize_user, check_id, check_new_account, initialize_pda_account, update_account,
+        write_in_place,
+    },
+};
+
+pub fn create_comment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    comment_id: String,
+    user_id: String,
+    text: String,
+    parent_comment: Option<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let post_account = next_account_info(account_info_iter)?;
+    let post_author_account = next_account_info(account_info_iter)?;
+    let comment_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let user = authorize_user(program_id, user_account, wallet_account)?;
+    if user.user_id != user_id {
+        msg!("Invalid user ID!");
+        return Err(AnimeNexaError::UserIdMismatch.into());
+    }
+
+    check_new_account(program_id, wallet_account, comment_account, system_program)?;
+    check_id(&comment_id)?;
+
+    if text.len() > MAX_COMMENT_LEN {
+        msg!("Comment is too long!");
+        return Err(AnimeNexaError::TextTooLong.into());
+    }
+
+    let mut post = load_post(program_id, post_author_account, post_account)?;
+    if post.deleted {
+        msg!("Post has been deleted!");
+        return Err(AnimeNexaError::PostDeleted.into());
+    }
+
+    // A reply must stay within the thread of the post it is made on
+    if let Some(parent_address) = parent_comment {
+        let parent_account = next_account_info(account_info_iter)?;
+        if *parent_account.key != parent_address {
+            msg!("Parent comment account does not match!");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut parent = load_comment(program_id, parent_account)?;
+        if parent.post != *post_account.key {
+            msg!("Parent comment is on another post!");
+            return Err(AnimeNexaError::CommentNotInThread.into());
+        }
+        if parent.deleted {
+            msg!("Parent comment has been deleted!");
+            return Err(AnimeNexaError::CommentDeleted.into());
+        }
+
+        parent.reply_count = parent
+            .reply_count
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        write_in_place(parent_account, &parent)?;
+    }
+
+    let (comment_address, bump) = find_comment_address(program_id, user_account.key, &comment_id);
+    if *comment_account.key != comment_address {
+        msg!("Comment account does not match the comment ID!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Create the comment
+    let comment = Comment {
+        comment_id,
+        user_id,
+        author: *user_account.key,
+        post: *post_account.key,
+        parent_comment,
+        text,
+        created_at: Clock::get()?.unix_timestamp,
+        reply_count: 0,
+        deleted: false,
+        reactions: ReactionCounts::default(),
+    };
+
+    initialize_pda_account(
+        wallet_account,
+        comment_account,
+        system_program,
+        program_id,
+        &comment,
+        &[
+            COMMENT_SEED,
+            user_account.key.as_ref(),
+            comment.comment_id.as_bytes(),
+            &[bump],
+        ],
+    )?;
+
+    post.comment_count = post
+        .comment_count
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    write_in_place(post_account, &post)?;
+
+    msg!("Comment created successfully: {:?}", comment);
+
+    Ok(())
+}
+
+// Turn one of the author's comments into a tombstone, refunding the freed rent.
+// The account stays behind so replies to it keep a valid parent.
+pub fn delete_comment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    comment_id: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let comment_account = next_account_info(account_info_iter)?;
+    let post_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    authorize_user(program_id, user_account, wallet_account)?;
+
+    let mut comment = load_comment(program_id, comment_account)?;
+    if comment.author != *user_account.key {
+        msg!("Comment was not written by the user!");
+        return Err(AnimeNexaError::Unauthorized.into());
+    }
+    if comment.comment_id != comment_id {
+        msg!("Comment ID does not match!");
+        return Err(AnimeNexaError::CommentIdMismatch.into());
+    }
+    if comment.deleted {
+        msg!("Comment has already been deleted!");
+        return Err(AnimeNexaError::CommentDeleted.into());
+    }
+
+    if *post_account.key != comment.post || post_account.owner != program_id {
+        msg!("Post account does not match the comment!");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    comment.text.clear();
+    comment.deleted = true;
+
+    // Shrinking the account returns the surplus rent to the author's wallet
+    update_account(comment_account, wallet_account, system_program, &comment)?;
+
+    // The thread's count only covers comments that can still be read
+    let mut post = Post::try_from_slice(&post_account.data.borrow())?;
+    post.comment_count = post.comment_count.saturating_sub(1);
+    write_in_place(post_account, &post)?;
+
+    // Likewise for the replies counted on the parent comment
+    if let Some(parent_address) = comment.parent_comment {
+        let parent_account = next_account_info(account_info_iter)?;
+        if *parent_account.key != parent_address {
+            msg!("Parent comment account does not match!");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut parent = load_comment(program_id, parent_account)?;
+        parent.reply_count = parent.reply_count.saturating_sub(1);
+        write_in_place(parent_account, &parent)?;
+    }
+
+    msg!("Comment deleted successfully: {}", comment.comment_id);
+
+    Ok(())
+}
+
+/// Load a comment, checking it is the PDA created for its author.
+pub fn load_comment(
+    program_id: &Pubkey,
+    comment_account: &AccountInfo,
+) -> Result<Comment, ProgramError> {
+    if comment_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let comment = Comment::try_from_slice(&comment_account.data.borrow())?;
+
+    let (comment_address, _) =
+        find_comment_address(program_id, &comment.author, &comment.comment_id);
+    if *comment_account.key != comment_address {
+        msg!("Comment account was not created by its author!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(comment)
+}
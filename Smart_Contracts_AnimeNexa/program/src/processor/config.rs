@@ -0,0 +1,120 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::AnimeNexaError,
+    pda::{find_config_address, CONFIG_SEED},
+    state::{config::BASIS_POINTS_DENOMINATOR, PlatformConfig},
+    utils::{check_new_account, initialize_pda_account, load_config, write_in_place},
+};
+
+// Create the platform config. Only the program's upgrade authority may do so,
+// which keeps anyone else from claiming the admin role first.
+pub fn initialize_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fee_basis_points: u16,
+    treasury: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let admin_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let program_data_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    check_new_account(program_id, admin_account, config_account, system_program)?;
+    check_fee(fee_basis_points)?;
+
+    let (program_data_address, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    if *program_data_account.key != program_data_address {
+        msg!("Invalid program data account!");
+        return Err(ProgramError::InvalidArgument);
+    }
+    let metadata_len = UpgradeableLoaderState::size_of_programdata_metadata();
+    let program_data = program_data_account.data.borrow();
+    let upgrade_authority = match program_data
+        .get(..metadata_len)
+        .map(bincode::deserialize::<UpgradeableLoaderState>)
+    {
+        Some(Ok(UpgradeableLoaderState::ProgramData {
+            upgrade_authority_address,
+            ..
+        })) => upgrade_authority_address,
+        _ => return Err(ProgramError::InvalidAccountData),
+    };
+    if upgrade_authority != Some(*admin_account.key) {
+        msg!("Only the upgrade authority may initialize the config!");
+        return Err(AnimeNexaError::Unauthorized.into());
+    }
+
+    let (config_address, bump) = find_config_address(program_id);
+    if *config_account.key != config_address {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let config = PlatformConfig {
+        admin: *admin_account.key,
+        treasury,
+        fee_basis_points,
+    };
+
+    initialize_pda_account(
+        admin_account,
+        config_account,
+        system_program,
+        program_id,
+        &config,
+        &[CONFIG_SEED, &[bump]],
+    )?;
+
+    msg!("Platform config initialized: {:?}", config);
+
+    Ok(())
+}
+
+pub fn update_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fee_basis_points: u16,
+    treasury: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let admin_account = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+
+    let mut config = load_config(program_id, config_account)?;
+
+    if !admin_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if config.admin != *admin_account.key {
+        msg!("Only the platform admin may update the config!");
+        return Err(AnimeNexaError::Unauthorized.into());
+    }
+
+    check_fee(fee_basis_points)?;
+
+    config.fee_basis_points = fee_basis_points;
+    config.treasury = treasury;
+
+    write_in_place(config_account, &config)?;
+
+    msg!("Platform config updated: {:?}", config);
+
+    Ok(())
+}
+
+fn check_fee(fee_basis_points: u16) -> ProgramResult {
+    if fee_basis_points > BASIS_POINTS_DENOMINATOR {
+        msg!("Platform fee cannot exceed 100%!");
+        return Err(AnimeNexaError::InvalidBasisPoints.into());
+    }
+    Ok(())
+}
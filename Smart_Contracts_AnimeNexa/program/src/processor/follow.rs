@@ -0,0 +1,122 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    error::AnimeNexaError,
+    pda::{find_follow_address, FOLLOW_SEED},
+    state::Follow,
+    utils::{
+        authorize_user, check_new_account, close_account, initialize_pda_account, load_user,
+        write_in_place,
+    },
+};
+
+pub fn follow(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let follower_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let followee_account = next_account_info(account_info_iter)?;
+    let follow_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let mut follower = authorize_user(program_id, follower_account, wallet_account)?;
+    let mut followee = load_user(program_id, followee_account)?;
+
+    if follower_account.key == followee_account.key {
+        msg!("Users cannot follow themselves!");
+        return Err(AnimeNexaError::CannotFollowSelf.into());
+    }
+
+    check_new_account(program_id, wallet_account, follow_account, system_program).map_err(
+        |error| match error {
+            ProgramError::AccountAlreadyInitialized => {
+                msg!("User is already followed!");
+                AnimeNexaError::AlreadyFollowing.into()
+            }
+            error => error,
+        },
+    )?;
+
+    let (follow_address, bump) =
+        find_follow_address(program_id, follower_account.key, followee_account.key);
+    if *follow_account.key != follow_address {
+        msg!("Follow account does not match the users!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let follow = Follow {
+        follower: *follower_account.key,
+        followee: *followee_account.key,
+        followed_at: Clock::get()?.unix_timestamp,
+    };
+
+    initialize_pda_account(
+        wallet_account,
+        follow_account,
+        system_program,
+        program_id,
+        &follow,
+        &[
+            FOLLOW_SEED,
+            follower_account.key.as_ref(),
+            followee_account.key.as_ref(),
+            &[bump],
+        ],
+    )?;
+
+    follower.following_count = follower
+        .following_count
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    followee.follower_count = followee
+        .follower_count
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    write_in_place(follower_account, &follower)?;
+    write_in_place(followee_account, &followee)?;
+
+    msg!("User followed successfully: {:?}", follow);
+
+    Ok(())
+}
+
+// Remove a follow edge, refunding its rent to the follower's wallet
+pub fn unfollow(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let follower_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let followee_account = next_account_info(account_info_iter)?;
+    let follow_account = next_account_info(account_info_iter)?;
+
+    let mut follower = authorize_user(program_id, follower_account, wallet_account)?;
+    let mut followee = load_user(program_id, followee_account)?;
+
+    if follow_account.owner != program_id {
+        msg!("User is not followed!");
+        return Err(AnimeNexaError::NotFollowing.into());
+    }
+
+    let (follow_address, _) =
+        find_follow_address(program_id, follower_account.key, followee_account.key);
+    if *follow_account.key != follow_address {
+        msg!("Follow account does not match the users!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    close_account(follow_account, wallet_account)?;
+
+    follower.following_count = follower.following_count.saturating_sub(1);
+    followee.follower_count = followee.follower_count.saturating_sub(1);
+    write_in_place(follower_account, &follower)?;
+    write_in_place(followee_account, &followee)?;
+
+    msg!("User unfollowed successfully: {}", followee.user_id);
+
+    Ok(())
+}
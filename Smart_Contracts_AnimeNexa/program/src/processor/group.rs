@@ -0,0 +1,421 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    error::AnimeNexaError,
+    pda::{find_group_address, find_group_message_address, GROUP_MESSAGE_SEED, GROUP_SEED},
+    state::{group::MAX_GROUP_MEMBERS, Group, GroupMember, GroupMessage, GroupRole},
+    utils::{
+        authorize_user, check_id, check_new_account, grow_account, initialize_pda_account,
+        load_user, shrink_account, write_in_place,
+    },
+};
+
+// Implementing the Create Group Logic
+//
+// The creator becomes the group's owner; every user account passed after the
+// fixed accounts joins as a plain member.
+pub fn create_group(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    group_id: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let group_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let user = authorize_user(program_id, user_account, wallet_account)?;
+
+    // An existing group is never overwritten
+    check_new_account(program_id, wallet_account, group_account, system_program)?;
+    check_id(&group_id)?;
+
+    let (group_address, bump) = find_group_address(program_id, user_account.key, &group_id);
+    if *group_account.key != group_address {
+        msg!("Group account does not match the group ID!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mut group = Group {
+        group_id,
+        creator: *user_account.key,
+        owner: *user_account.key,
+        members: vec![GroupMember {
+            user_id: user.user_id,
+            user: *user_account.key,
+            role: GroupRole::Owner,
+        }],
+    };
+
+    for member_account in account_info_iter {
+        add_to_group(program_id, &mut group, member_account)?;
+    }
+
+    initialize_pda_account(
+        wallet_account,
+        group_account,
+        system_program,
+        program_id,
+        &group,
+        &[
+            GROUP_SEED,
+            user_account.key.as_ref(),
+            group.group_id.as_bytes(),
+            &[bump],
+        ],
+    )?;
+
+    msg!("Group created successfully: {:?}", group);
+
+    Ok(())
+}
+
+// Add a user to the group; moderators and above may add members
+pub fn add_member(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    group_id: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let group_account = next_account_info(account_info_iter)?;
+    let member_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let (mut group, role) = load_group_as_member(
+        program_id,
+        user_account,
+        wallet_account,
+        group_account,
+        &group_id,
+    )?;
+    require_outranks(role, GroupRole::Member)?;
+
+    add_to_group(program_id, &mut group, member_account)?;
+
+    // Rent left behind by removed members is used before the wallet pays
+    grow_account(group_account, wallet_account, system_program, &group)?;
+
+    msg!("Group member added successfully: {:?}", group);
+
+    Ok(())
+}
+
+// Remove a member the acting user outranks
+pub fn remove_member(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    group_id: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let group_account = next_account_info(account_info_iter)?;
+    let member_account = next_account_info(account_info_iter)?;
+
+    let (mut group, role) = load_group_as_member(
+        program_id,
+        user_account,
+        wallet_account,
+        group_account,
+        &group_id,
+    )?;
+
+    let member = group.member(member_account.key).ok_or_else(not_a_member)?;
+    require_outranks(role, member.role)?;
+
+    group
+        .members
+        .retain(|member| member.user != *member_account.key);
+
+    // The freed rent was paid by whoever added the member, so it stays with
+    // the group and funds the next member added
+    shrink_account(group_account, &group)?;
+
+    msg!("Group member removed successfully: {:?}", group);
+
+    Ok(())
+}
+
+// Leave a group; the owner has to hand the group over first
+pub fn leave_group(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    group_id: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let group_account = next_account_info(account_info_iter)?;
+
+    let (mut group, role) = load_group_as_member(
+        program_id,
+        user_account,
+        wallet_account,
+        group_account,
+        &group_id,
+    )?;
+    if role == GroupRole::Owner {
+        msg!("Group owner cannot leave the group!");
+        return Err(AnimeNexaError::OwnerCannotLeave.into());
+    }
+
+    group
+        .members
+        .retain(|member| member.user != *user_account.key);
+
+    // The leaving member did not necessarily pay for their entry, so the freed
+    // rent stays with the group
+    shrink_account(group_account, &group)?;
+
+    msg!("Group left successfully: {:?}", group);
+
+    Ok(())
+}
+
+// Hand the group over to another member; the previous owner stays on as an admin
+pub fn transfer_ownership(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    group_id: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let group_account = next_account_info(account_info_iter)?;
+    let new_owner_account = next_account_info(account_info_iter)?;
+
+    let (mut group, role) = load_group_as_member(
+        program_id,
+        user_account,
+        wallet_account,
+        group_account,
+        &group_id,
+    )?;
+    if role != GroupRole::Owner {
+        msg!("Only the group owner can transfer ownership!");
+        return Err(AnimeNexaError::InsufficientRole.into());
+    }
+    if new_owner_account.key == user_account.key {
+        msg!("User already owns the group!");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    group
+        .member_mut(new_owner_account.key)
+        .ok_or_else(not_a_member)?
+        .role = GroupRole::Owner;
+    if let Some(previous_owner) = group.member_mut(user_account.key) {
+        previous_owner.role = GroupRole::Admin;
+    }
+    group.owner = *new_owner_account.key;
+
+    write_in_place(group_account, &group)?;
+
+    msg!("Group ownership transferred successfully: {:?}", group);
+
+    Ok(())
+}
+
+// Change a member's role. Admins and the owner may only change the role of
+// members they outrank, and only to a role they outrank.
+pub fn set_role(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    group_id: String,
+    role: GroupRole,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let group_account = next_account_info(account_info_iter)?;
+    let member_account = next_account_info(account_info_iter)?;
+
+    let (mut group, actor_role) = load_group_as_member(
+        program_id,
+        user_account,
+        wallet_account,
+        group_account,
+        &group_id,
+    )?;
+    require_outranks(actor_role, GroupRole::Moderator)?;
+
+    let member = group
+        .member_mut(member_account.key)
+        .ok_or_else(not_a_member)?;
+    require_outranks(actor_role, member.role)?;
+    require_outranks(actor_role, role)?;
+    member.role = role;
+
+    write_in_place(group_account, &group)?;
+
+    msg!("Group role updated successfully: {:?}", group);
+
+    Ok(())
+}
+
+// Implementing the Send Group Message Logic
+pub fn send_group_message(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    message_id: String,
+    group_id: String,
+    user_id: String,
+    message_content: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let group_account = next_account_info(account_info_iter)?;
+    let message_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let user = authorize_user(program_id, user_account, wallet_account)?;
+    if user.user_id != user_id {
+        msg!("Invalid user ID!");
+        return Err(AnimeNexaError::UserIdMismatch.into());
+    }
+
+    // Deserialize the group account to check if the user is a member
+    let group = load_group(program_id, group_account, &group_id)?;
+
+    if group.member(user_account.key).is_none() {
+        msg!("User is not a member of the group!");
+        return Err(AnimeNexaError::NotGroupMember.into());
+    }
+
+    // Messages are always stored in a fresh PDA, never in a caller-supplied account
+    check_new_account(program_id, wallet_account, message_account, system_program)?;
+    check_id(&message_id)?;
+
+    let (message_address, bump) =
+        find_group_message_address(program_id, group_account.key, user_account.key, &message_id);
+    if *message_account.key != message_address {
+        msg!("Group message account does not match the message ID!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Initialize the Group Message struct with the current timestamp
+    let clock = Clock::get()?;
+    let timestamp = clock.unix_timestamp as u64;
+
+    let message = GroupMessage {
+        message_id,
+        group_id,
+        user_id,
+        message_content, // Encrypt this content before sending
+        timestamp,
+    };
+
+    initialize_pda_account(
+        wallet_account,
+        message_account,
+        system_program,
+        program_id,
+        &message,
+        &[
+            GROUP_MESSAGE_SEED,
+            group_account.key.as_ref(),
+            user_account.key.as_ref(),
+            message.message_id.as_bytes(),
+            &[bump],
+        ],
+    )?;
+
+    msg!("Group message sent successfully: {:?}", message);
+
+    Ok(())
+}
+
+/// Load a group, checking it is the PDA created for its creator.
+pub fn load_group(
+    program_id: &Pubkey,
+    group_account: &AccountInfo,
+    group_id: &str,
+) -> Result<Group, ProgramError> {
+    if group_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let group = Group::try_from_slice(&group_account.data.borrow())?;
+
+    let (group_address, _) = find_group_address(program_id, &group.creator, &group.group_id);
+    if *group_account.key != group_address {
+        msg!("Group account was not created by its creator!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if group.group_id != group_id {
+        msg!("Group ID does not match!");
+        return Err(AnimeNexaError::GroupIdMismatch.into());
+    }
+
+    Ok(group)
+}
+
+// Authorize the acting user and load the group along with their role in it
+fn load_group_as_member(
+    program_id: &Pubkey,
+    user_account: &AccountInfo,
+    wallet_account: &AccountInfo,
+    group_account: &AccountInfo,
+    group_id: &str,
+) -> Result<(Group, GroupRole), ProgramError> {
+    authorize_user(program_id, user_account, wallet_account)?;
+
+    let group = load_group(program_id, group_account, group_id)?;
+    let role = group
+        .member(user_account.key)
+        .ok_or_else(not_a_member)?
+        .role;
+
+    Ok((group, role))
+}
+
+// Add a registered user to the group as a plain member
+fn add_to_group(
+    program_id: &Pubkey,
+    group: &mut Group,
+    member_account: &AccountInfo,
+) -> ProgramResult {
+    let member = load_user(program_id, member_account)?;
+
+    if group.member(member_account.key).is_some() {
+        msg!("User {} is already a member of the group!", member.user_id);
+        return Err(AnimeNexaError::AlreadyGroupMember.into());
+    }
+    if group.members.len() >= MAX_GROUP_MEMBERS {
+        msg!("Group already has {} members!", MAX_GROUP_MEMBERS);
+        return Err(AnimeNexaError::GroupFull.into());
+    }
+
+    group.members.push(GroupMember {
+        user_id: member.user_id,
+        user: *member_account.key,
+        role: GroupRole::Member,
+    });
+
+    Ok(())
+}
+
+fn require_outranks(role: GroupRole, other: GroupRole) -> ProgramResult {
+    if !role.outranks(other) {
+        msg!("{:?} role cannot act on {:?}!", role, other);
+        return Err(AnimeNexaError::InsufficientRole.into());
+    }
+    Ok(())
+}
+
+fn not_a_member() -> ProgramError {
+    msg!("User is not a member of the group!");
+    AnimeNexaError::NotGroupMember.into()
+}
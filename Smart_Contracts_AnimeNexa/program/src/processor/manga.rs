@@ -0,0 +1,358 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    error::AnimeNexaError,
+    pda::{
+        find_chapter_address, find_content_address, find_manga_access_address, find_manga_address,
+        find_receipt_address, MANGA_ACCESS_SEED, MANGA_SEED,
+    },
+    state::{AccessGrant, Manga, MangaAccess, Purchase, ReactionCounts},
+    utils::{
+        authorize_user, check_id, check_new_account, initialize_pda_account, load_user,
+        update_account,
+    },
+};
+
+#[allow(clippy::too_many_arguments)]
+pub fn upload_manga(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    manga_id: String,
+    creator_user_id: String,
+    title: String,
+    description: String,
+    timestamp: u64,
+    is_free: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let creator_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let manga_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // Deserialize the creator's account data
+    let creator = authorize_user(program_id, creator_account, wallet_account)?;
+
+    check_new_account(program_id, payer_account, manga_account, system_program)?;
+    check_id(&manga_id)?;
+
+    // Validate that the user is the creator
+    if creator.user_id != creator_user_id {
+        msg!("Invalid creator user ID!");
+        return Err(AnimeNexaError::NotCreator.into());
+    }
+
+    let (manga_address, bump) = find_manga_address(program_id, creator_account.key, &manga_id);
+    if *manga_account.key != manga_address {
+        msg!("Manga account does not match the manga ID!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Initialize manga data
+    let manga = Manga {
+        manga_id,
+        creator_user_id,
+        title,
+        description,
+        timestamp,
+        is_free,
+        chapter_count: 0,
+        free_chapters: 0,
+        reactions: ReactionCounts::default(),
+    };
+
+    initialize_pda_account(
+        payer_account,
+        manga_account,
+        system_program,
+        program_id,
+        &manga,
+        &[
+            MANGA_SEED,
+            creator_account.key.as_ref(),
+            manga.manga_id.as_bytes(),
+            &[bump],
+        ],
+    )?;
+
+    msg!("Manga uploaded successfully: {:?}", manga);
+
+    Ok(())
+}
+
+// Implementing the Manga Access Logic
+//
+// Access is granted to the manga's creator, to anyone when the manga is free,
+// and otherwise from a purchase receipt: one for the monetized content sharing
+// the manga's ID buys the series, one keyed by a chapter buys that chapter.
+// The first `free_chapters` chapters need no receipt at all.
+pub fn access_manga(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    manga_id: String,
+    user_id: String,
+    chapter: Option<u32>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let manga_access_account = next_account_info(account_info_iter)?;
+    let manga_account = next_account_info(account_info_iter)?;
+    let creator_account = next_account_info(account_info_iter)?;
+    let receipt_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // Deserialize the user's account data to check if the user exists
+    let user = authorize_user(program_id, user_account, wallet_account)?;
+
+    // Validate that the User ID matches
+    if user.user_id != user_id {
+        msg!("User ID does not match!");
+        return Err(AnimeNexaError::UserIdMismatch.into());
+    }
+
+    let manga = load_manga(program_id, creator_account, manga_account)?;
+    if manga.manga_id != manga_id {
+        msg!("Manga ID does not match!");
+        return Err(AnimeNexaError::MangaIdMismatch.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let chapter = match chapter {
+        Some(chapter) => Some((chapter, next_account_info(account_info_iter)?)),
+        None => None,
+    };
+    let grant = resolve_manga_grant(
+        program_id,
+        user_account,
+        manga_account,
+        creator_account,
+        &manga,
+        receipt_account,
+        chapter,
+        now,
+    )?;
+
+    let manga_access = MangaAccess {
+        manga_id,
+        user_id,
+        grant,
+        granted_at: now,
+    };
+
+    // Initialize or update the Manga Access data
+    if manga_access_account.owner == program_id {
+        let (manga_access_address, _) =
+            find_manga_access_address(program_id, manga_account.key, user_account.key);
+        if *manga_access_account.key != manga_access_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        update_account(
+            manga_access_account,
+            wallet_account,
+            system_program,
+            &manga_access,
+        )?;
+    } else {
+        check_new_account(
+            program_id,
+            wallet_account,
+            manga_access_account,
+            system_program,
+        )?;
+
+        let (manga_access_address, bump) =
+            find_manga_access_address(program_id, manga_account.key, user_account.key);
+        if *manga_access_account.key != manga_access_address {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        initialize_pda_account(
+            wallet_account,
+            manga_access_account,
+            system_program,
+            program_id,
+            &manga_access,
+            &[
+                MANGA_ACCESS_SEED,
+                manga_account.key.as_ref(),
+                user_account.key.as_ref(),
+                &[bump],
+            ],
+        )?;
+    }
+
+    msg!("Manga access granted successfully: {:?}", manga_access);
+
+    Ok(())
+}
+
+/// Load a manga, checking it is the PDA created for `creator_account`.
+pub fn load_manga(
+    program_id: &Pubkey,
+    creator_account: &AccountInfo,
+    manga_account: &AccountInfo,
+) -> Result<Manga, ProgramError> {
+    if manga_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let manga = Manga::try_from_slice(&manga_account.data.borrow())?;
+
+    let (manga_address, _) = find_manga_address(program_id, creator_account.key, &manga.manga_id);
+    if *manga_account.key != manga_address {
+        msg!("Manga account was not created by the creator!");
+        return Err(AnimeNexaError::NotCreator.into());
+    }
+
+    Ok(manga)
+}
+
+/// Resolve the grant through which `user_account` may read `manga`, or one of
+/// its chapters when `chapter` comes with that chapter's receipt account.
+///
+/// The creator and readers of a free manga get permanent access. Otherwise the
+/// series receipt and the free preview are consulted and, for a chapter, its
+/// own receipt as well; the first grant that applies right now is returned.
+#[allow(clippy::too_many_arguments)]
+pub fn resolve_manga_grant(
+    program_id: &Pubkey,
+    user_account: &AccountInfo,
+    manga_account: &AccountInfo,
+    creator_account: &AccountInfo,
+    manga: &Manga,
+    series_receipt_account: &AccountInfo,
+    chapter: Option<(u32, &AccountInfo)>,
+    now: i64,
+) -> Result<AccessGrant, ProgramError> {
+    if user_account.key == creator_account.key || manga.is_free {
+        return Ok(AccessGrant::Permanent);
+    }
+
+    let (content_address, _) =
+        find_content_address(program_id, creator_account.key, &manga.manga_id);
+    let (series_receipt_address, _) =
+        find_receipt_address(program_id, &content_address, user_account.key);
+    let series_grant = receipt_grant(
+        series_receipt_account,
+        program_id,
+        &series_receipt_address,
+        &content_address,
+    )?;
+    let free_preview = (manga.free_chapters > 0).then_some(AccessGrant::ChapterRange {
+        first_chapter: 1,
+        last_chapter: manga.free_chapters,
+    });
+
+    let granted = match chapter {
+        None => series_grant
+            .filter(|grant| grant.is_live(now))
+            .or(free_preview),
+        Some((chapter, chapter_receipt_account)) => {
+            let (chapter_address, _) = find_chapter_address(program_id, manga_account.key, chapter);
+            let (chapter_receipt_address, _) =
+                find_receipt_address(program_id, &chapter_address, user_account.key);
+
+            series_grant
+                .filter(|grant| grant.covers_chapter(chapter, now))
+                .or(free_preview.filter(|grant| grant.covers_chapter(chapter, now)))
+                .or(receipt_grant(
+                    chapter_receipt_account,
+                    program_id,
+                    &chapter_receipt_address,
+                    &content_address,
+                )?)
+        }
+    };
+
+    match granted {
+        Some(grant) => Ok(grant),
+        None if series_grant.is_some_and(|grant| !grant.is_live(now)) => {
+            msg!("Manga rental has expired!");
+            Err(AnimeNexaError::AccessExpired.into())
+        }
+        None if series_grant.is_some() => {
+            msg!("Manga purchase does not cover the chapter!");
+            Err(AnimeNexaError::ChapterNotCovered.into())
+        }
+        None => {
+            msg!("Manga has not been purchased!");
+            Err(AnimeNexaError::MangaNotPurchased.into())
+        }
+    }
+}
+
+/// Grant recorded by `receipt_account` for `content_address`, if it is the
+/// initialized receipt expected at `receipt_address`.
+pub fn receipt_grant(
+    receipt_account: &AccountInfo,
+    program_id: &Pubkey,
+    receipt_address: &Pubkey,
+    content_address: &Pubkey,
+) -> Result<Option<AccessGrant>, ProgramError> {
+    if receipt_account.owner != program_id || receipt_account.key != receipt_address {
+        return Ok(None);
+    }
+
+    let receipt = Purchase::try_from_slice(&receipt_account.data.borrow())?;
+    Ok((receipt.content == *content_address).then_some(receipt.grant))
+}
+
+// Check whether the user can read the manga right now, or `chapter` if given,
+// resolving access from their receipts the same way AccessManga does. Nothing
+// is written, so readers can simulate this instruction to ask the chain whether
+// a grant is valid right now.
+pub fn check_manga_access(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    manga_id: String,
+    user_id: String,
+    chapter: Option<u32>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+    let manga_account = next_account_info(account_info_iter)?;
+    let creator_account = next_account_info(account_info_iter)?;
+    let receipt_account = next_account_info(account_info_iter)?;
+
+    let user = load_user(program_id, user_account)?;
+    if user.user_id != user_id {
+        msg!("User ID does not match!");
+        return Err(AnimeNexaError::UserIdMismatch.into());
+    }
+
+    let manga = load_manga(program_id, creator_account, manga_account)?;
+    if manga.manga_id != manga_id {
+        msg!("Manga ID does not match!");
+        return Err(AnimeNexaError::MangaIdMismatch.into());
+    }
+
+    let chapter = match chapter {
+        Some(chapter) => Some((chapter, next_account_info(account_info_iter)?)),
+        None => None,
+    };
+    let grant = resolve_manga_grant(
+        program_id,
+        user_account,
+        manga_account,
+        creator_account,
+        &manga,
+        receipt_account,
+        chapter,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    msg!("Manga access is live: {:?}", grant);
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::AnimeNexaError,
+    pda::{find_media_address, MEDIA_SEED},
+    processor::post::load_post,
+    state::{post::MAX_POST_MEDIA, Media, MediaReference, MediaType},
+    utils::{
+        authorize_user, check_id, check_media, check_new_account, initialize_pda_account,
+        update_account,
+    },
+};
+
+pub fn upload_media(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    media_id: String,
+    post_id: String,
+    user_id: String,
+    reference: MediaReference,
+    media_type: MediaType,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let post_account = next_account_info(account_info_iter)?;
+    let media_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // Deserialize the user and post data to validate
+    let user = authorize_user(program_id, user_account, wallet_account)?;
+
+    // Media can only be attached to the uploader's own posts
+    let mut post = load_post(program_id, user_account, post_account)?;
+    if post.post_id != post_id {
+        msg!("Post ID does not match!");
+        return Err(AnimeNexaError::PostIdMismatch.into());
+    }
+    if post.deleted {
+        msg!("Post has been deleted!");
+        return Err(AnimeNexaError::PostDeleted.into());
+    }
+    if post.media_ids.len() >= MAX_POST_MEDIA {
+        msg!("Post already has {} media attached!", MAX_POST_MEDIA);
+        return Err(AnimeNexaError::TooManyMedia.into());
+    }
+
+    check_new_account(program_id, payer_account, media_account, system_program)?;
+    check_id(&media_id)?;
+
+    check_media(&reference, media_type)?;
+
+    // Validate that the user exists
+    if user.user_id != user_id {
+        msg!("Invalid user ID!");
+        return Err(AnimeNexaError::UserIdMismatch.into());
+    }
+
+    let (media_address, bump) = find_media_address(program_id, user_account.key, &media_id);
+    if *media_account.key != media_address {
+        msg!("Media account does not match the media ID!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Link media to the post
+    let media = Media {
+        media_id,
+        post_id,
+        user_id,
+        reference,
+        media_type,
+    };
+
+    initialize_pda_account(
+        payer_account,
+        media_account,
+        system_program,
+        program_id,
+        &media,
+        &[
+            MEDIA_SEED,
+            user_account.key.as_ref(),
+            media.media_id.as_bytes(),
+            &[bump],
+        ],
+    )?;
+
+    post.media_ids.push(media.media_id.clone());
+    update_account(post_account, payer_account, system_program, &post)?;
+
+    msg!("Media uploaded successfully: {:?}", media);
+
+    Ok(())
+}
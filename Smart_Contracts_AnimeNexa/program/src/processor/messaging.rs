@@ -0,0 +1,84 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    error::AnimeNexaError,
+    pda::{find_message_address, MESSAGE_SEED},
+    state::Message,
+    utils::{authorize_user, check_id, check_new_account, initialize_pda_account, load_user},
+};
+
+// Implementing the SendMessage Logic
+pub fn send_message(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    message_id: String,
+    sender_user_id: String,
+    recipient_user_id: String,
+    message_content: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let sender_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let recipient_account = next_account_info(account_info_iter)?;
+    let message_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let sender = authorize_user(program_id, sender_account, wallet_account)?;
+    if sender.user_id != sender_user_id {
+        msg!("Invalid sender user ID!");
+        return Err(AnimeNexaError::UserIdMismatch.into());
+    }
+
+    let recipient = load_user(program_id, recipient_account)?;
+    if recipient.user_id != recipient_user_id {
+        msg!("Invalid recipient user ID!");
+        return Err(AnimeNexaError::UserIdMismatch.into());
+    }
+
+    // Messages are always stored in a fresh PDA, never in a caller-supplied account
+    check_new_account(program_id, wallet_account, message_account, system_program)?;
+    check_id(&message_id)?;
+
+    let (message_address, bump) = find_message_address(program_id, sender_account.key, &message_id);
+    if *message_account.key != message_address {
+        msg!("Message account does not match the message ID!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Initialize the Message struct with the current timestamp
+    let clock = Clock::get()?;
+    let timestamp = clock.unix_timestamp as u64;
+
+    let message = Message {
+        message_id,
+        sender_user_id,
+        recipient_user_id,
+        message_content, // Encrypt this content before sending
+        timestamp,
+    };
+
+    initialize_pda_account(
+        wallet_account,
+        message_account,
+        system_program,
+        program_id,
+        &message,
+        &[
+            MESSAGE_SEED,
+            sender_account.key.as_ref(),
+            message.message_id.as_bytes(),
+            &[bump],
+        ],
+    )?;
+
+    msg!("Message sent successfully: {:?}", message);
+
+    Ok(())
+}
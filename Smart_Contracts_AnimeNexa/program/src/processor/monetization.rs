@@ -0,0 +1,589 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    error::AnimeNexaError,
+    payment::{split_amount, PaymentRoute},
+    pda::{find_content_address, find_receipt_address, CONTENT_SEED, RECEIPT_SEED},
+    processor::{
+        chapter::load_chapter,
+        manga::{load_manga, receipt_grant},
+    },
+    state::{
+        config::BASIS_POINTS_DENOMINATOR, monetization::MAX_REVENUE_SHARES, AccessGrant,
+        AccessTerms, MonetizedContent, Payout, PlatformConfig, Purchase, RevenueShare,
+    },
+    utils::{
+        authorize_user, check_id, check_new_account, initialize_pda_account, load_config,
+        load_user, update_account, write_in_place,
+    },
+};
+
+pub fn monetize_content(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    content_id: String,
+    creator_user_id: String,
+    price: u64,
+    payment_mint: Option<Pubkey>,
+    access_terms: AccessTerms,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let creator_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let content_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // Deserialize the creator's account data
+    let creator = authorize_user(program_id, creator_account, wallet_account)?;
+
+    // Validate that the user is the creator
+    if creator.user_id != creator_user_id {
+        msg!("Invalid creator user ID!");
+        return Err(AnimeNexaError::NotCreator.into());
+    }
+
+    check_id(&content_id)?;
+
+    if !access_terms.is_valid() {
+        msg!("Invalid access terms!");
+        return Err(AnimeNexaError::InvalidAccessTerms.into());
+    }
+
+    // Update already monetized content in place
+    if content_account.owner == program_id {
+        let mut content = load_content(program_id, creator_account, content_account)?;
+        if content.content_id != content_id {
+            msg!("Content ID does not match!");
+            return Err(AnimeNexaError::ContentIdMismatch.into());
+        }
+
+        // Sale totals are kept in the payment currency, so it cannot change
+        // once the content has been sold
+        if content.total_sales > 0 && content.payment_mint != payment_mint {
+            msg!("Payment mint cannot change after the first sale!");
+            return Err(AnimeNexaError::PaymentMintLocked.into());
+        }
+
+        content.price = price;
+        content.payment_mint = payment_mint;
+        content.access_terms = access_terms;
+
+        // Serialize the content data back to the account
+        update_account(content_account, wallet_account, system_program, &content)?;
+
+        msg!("Content monetization updated successfully: {:?}", content);
+
+        return Ok(());
+    }
+
+    check_new_account(program_id, wallet_account, content_account, system_program)?;
+
+    let (content_address, bump) =
+        find_content_address(program_id, creator_account.key, &content_id);
+    if *content_account.key != content_address {
+        msg!("Content account does not match the content ID!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Initialize monetized content
+    let content = MonetizedContent {
+        content_id,
+        creator_user_id,
+        price,
+        payment_mint,
+        revenue_shares: vec![],
+        access_terms,
+        total_sales: 0,
+        total_revenue: 0,
+    };
+
+    initialize_pda_account(
+        wallet_account,
+        content_account,
+        system_program,
+        program_id,
+        &content,
+        &[
+            CONTENT_SEED,
+            creator_account.key.as_ref(),
+            content.content_id.as_bytes(),
+            &[bump],
+        ],
+    )?;
+
+    msg!("Content monetized successfully: {:?}", content);
+
+    Ok(())
+}
+
+// Replace the split table of monetized content
+pub fn set_revenue_shares(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    content_id: String,
+    revenue_shares: Vec<RevenueShare>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let creator_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let content_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    authorize_user(program_id, creator_account, wallet_account)?;
+
+    let mut content = load_content(program_id, creator_account, content_account)?;
+
+    if content.content_id != content_id {
+        msg!("Content ID does not match!");
+        return Err(AnimeNexaError::ContentIdMismatch.into());
+    }
+
+    // An empty table leaves the whole net amount to the creator
+    let total = revenue_shares
+        .iter()
+        .try_fold(0u16, |total, share| total.checked_add(share.basis_points));
+    if revenue_shares.len() > MAX_REVENUE_SHARES
+        || (!revenue_shares.is_empty() && total != Some(BASIS_POINTS_DENOMINATOR))
+    {
+        msg!("Invalid revenue shares!");
+        return Err(AnimeNexaError::InvalidRevenueShares.into());
+    }
+
+    content.revenue_shares = revenue_shares;
+
+    // Serialize the content data back to the account
+    update_account(content_account, wallet_account, system_program, &content)?;
+
+    msg!("Revenue shares updated successfully: {:?}", content);
+
+    Ok(())
+}
+
+pub fn purchase_content(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    content_id: String,
+    purchaser_user_id: String,
+    amount: u64,
+    timestamp: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let creator_account = next_account_info(account_info_iter)?;
+    let purchaser_account = next_account_info(account_info_iter)?;
+    let content_account = next_account_info(account_info_iter)?;
+    let purchaser_user_account = next_account_info(account_info_iter)?;
+    let creator_user_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let treasury_account = next_account_info(account_info_iter)?;
+    let receipt_account = next_account_info(account_info_iter)?;
+
+    // The purchaser's wallet pays, so it must be the one signing
+    let purchaser = authorize_user(program_id, purchaser_user_account, purchaser_account)?;
+    if purchaser.user_id != purchaser_user_id {
+        msg!("Invalid purchaser user ID!");
+        return Err(AnimeNexaError::UserIdMismatch.into());
+    }
+
+    // Deserialize the content data to validate purchase
+    let mut content = load_content(program_id, creator_user_account, content_account)?;
+
+    if content.content_id != content_id {
+        msg!("Content ID does not match!");
+        return Err(AnimeNexaError::ContentIdMismatch.into());
+    }
+
+    // Ensure the purchase amount matches the content's price
+    if content.price != amount {
+        msg!("Incorrect amount for purchasing content!");
+        return Err(AnimeNexaError::PriceMismatch.into());
+    }
+
+    // Payment must go to the wallet of the user who monetized the content
+    let creator = load_user(program_id, creator_user_account)?;
+    if creator.user_id != content.creator_user_id {
+        msg!("Creator account does not match the content!");
+        return Err(AnimeNexaError::NotCreator.into());
+    }
+    if creator.public_key != *creator_account.key {
+        msg!("Payee is not the creator's wallet!");
+        return Err(AnimeNexaError::WalletMismatch.into());
+    }
+
+    let (receipt_address, receipt_bump) =
+        find_receipt_address(program_id, content_account.key, purchaser_user_account.key);
+    if *receipt_account.key != receipt_address {
+        msg!("Receipt account does not match the purchase!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // Each user buys a piece of content once, unless an earlier rental has run
+    // out; the receipt proves the purchase
+    let renewing = receipt_account.owner == program_id;
+    if renewing {
+        let previous = Purchase::try_from_slice(&receipt_account.data.borrow())?;
+        if previous.grant.is_live(now) {
+            msg!("Content has already been purchased!");
+            return Err(AnimeNexaError::AlreadyPurchased.into());
+        }
+    } else {
+        check_new_account(
+            program_id,
+            purchaser_account,
+            receipt_account,
+            system_program,
+        )?;
+    }
+
+    let grant = content
+        .access_terms
+        .grant_at(now)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let config = load_config(program_id, config_account)?;
+    let (platform_fee, payouts) = collect_payment(
+        account_info_iter,
+        &content,
+        &config,
+        purchaser_account,
+        creator_account,
+        treasury_account,
+        system_program,
+        amount,
+    )?;
+
+    // Record the purchase as its own receipt account
+    let purchase = Purchase {
+        content: *content_account.key,
+        content_id,
+        purchaser_user_id,
+        purchaser: *purchaser_account.key,
+        amount,
+        payment_mint: content.payment_mint,
+        timestamp,
+        grant,
+        platform_fee,
+        payouts,
+    };
+
+    if renewing {
+        update_account(
+            receipt_account,
+            purchaser_account,
+            system_program,
+            &purchase,
+        )?;
+    } else {
+        initialize_pda_account(
+            purchaser_account,
+            receipt_account,
+            system_program,
+            program_id,
+            &purchase,
+            &[
+                RECEIPT_SEED,
+                content_account.key.as_ref(),
+                purchaser_user_account.key.as_ref(),
+                &[receipt_bump],
+            ],
+        )?;
+    }
+
+    record_sale(content_account, &mut content, amount)?;
+
+    msg!("Content purchased successfully: {:?}", purchase);
+
+    Ok(())
+}
+
+// Buy a single chapter of a manga sold through the monetized content sharing
+// its ID. The series' payment mint, revenue shares and platform fee apply;
+// only the price comes from the chapter.
+#[allow(clippy::too_many_arguments)]
+pub fn purchase_chapter(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    manga_id: String,
+    chapter_number: u32,
+    purchaser_user_id: String,
+    amount: u64,
+    timestamp: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let creator_account = next_account_info(account_info_iter)?;
+    let purchaser_account = next_account_info(account_info_iter)?;
+    let content_account = next_account_info(account_info_iter)?;
+    let purchaser_user_account = next_account_info(account_info_iter)?;
+    let creator_user_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let config_account = next_account_info(account_info_iter)?;
+    let treasury_account = next_account_info(account_info_iter)?;
+    let receipt_account = next_account_info(account_info_iter)?;
+    let manga_account = next_account_info(account_info_iter)?;
+    let chapter_account = next_account_info(account_info_iter)?;
+    let series_receipt_account = next_account_info(account_info_iter)?;
+
+    // The purchaser's wallet pays, so it must be the one signing
+    let purchaser = authorize_user(program_id, purchaser_user_account, purchaser_account)?;
+    if purchaser.user_id != purchaser_user_id {
+        msg!("Invalid purchaser user ID!");
+        return Err(AnimeNexaError::UserIdMismatch.into());
+    }
+
+    let manga = load_manga(program_id, creator_user_account, manga_account)?;
+    if manga.manga_id != manga_id {
+        msg!("Manga ID does not match!");
+        return Err(AnimeNexaError::MangaIdMismatch.into());
+    }
+
+    let chapter = load_chapter(program_id, manga_account, chapter_account, chapter_number)?;
+    if manga.is_free || chapter_number <= manga.free_chapters {
+        msg!("Chapter {} is free to read!", chapter_number);
+        return Err(AnimeNexaError::ChapterNotForSale.into());
+    }
+    if chapter.price == 0 {
+        msg!(
+            "Chapter {} is only sold with the whole series!",
+            chapter_number
+        );
+        return Err(AnimeNexaError::ChapterNotForSale.into());
+    }
+    if chapter.price != amount {
+        msg!("Incorrect amount for purchasing chapter!");
+        return Err(AnimeNexaError::PriceMismatch.into());
+    }
+
+    let mut content = load_content(program_id, creator_user_account, content_account)?;
+    if content.content_id != manga_id {
+        msg!("Content ID does not match the manga!");
+        return Err(AnimeNexaError::ContentIdMismatch.into());
+    }
+
+    // Payment must go to the wallet of the user who monetized the content
+    let creator = load_user(program_id, creator_user_account)?;
+    if creator.public_key != *creator_account.key {
+        msg!("Payee is not the creator's wallet!");
+        return Err(AnimeNexaError::WalletMismatch.into());
+    }
+
+    // A series purchase that still covers the chapter makes buying it pointless
+    let (series_receipt_address, _) =
+        find_receipt_address(program_id, content_account.key, purchaser_user_account.key);
+    if *series_receipt_account.key != series_receipt_address {
+        msg!("Series receipt account does not match the purchaser!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let now = Clock::get()?.unix_timestamp;
+    let series_grant = receipt_grant(
+        series_receipt_account,
+        program_id,
+        &series_receipt_address,
+        content_account.key,
+    )?;
+    if series_grant.is_some_and(|grant| grant.covers_chapter(chapter_number, now)) {
+        msg!("Chapter is already covered by the series purchase!");
+        return Err(AnimeNexaError::AlreadyPurchased.into());
+    }
+
+    // Chapter receipts are keyed by the chapter account, and never expire
+    check_new_account(
+        program_id,
+        purchaser_account,
+        receipt_account,
+        system_program,
+    )
+    .map_err(|error| match error {
+        ProgramError::AccountAlreadyInitialized => {
+            msg!("Chapter has already been purchased!");
+            AnimeNexaError::AlreadyPurchased.into()
+        }
+        error => error,
+    })?;
+
+    let (receipt_address, receipt_bump) =
+        find_receipt_address(program_id, chapter_account.key, purchaser_user_account.key);
+    if *receipt_account.key != receipt_address {
+        msg!("Receipt account does not match the purchase!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let config = load_config(program_id, config_account)?;
+    let (platform_fee, payouts) = collect_payment(
+        account_info_iter,
+        &content,
+        &config,
+        purchaser_account,
+        creator_account,
+        treasury_account,
+        system_program,
+        amount,
+    )?;
+
+    let purchase = Purchase {
+        content: *content_account.key,
+        content_id: manga_id,
+        purchaser_user_id,
+        purchaser: *purchaser_account.key,
+        amount,
+        payment_mint: content.payment_mint,
+        timestamp,
+        grant: AccessGrant::ChapterRange {
+            first_chapter: chapter_number,
+            last_chapter: chapter_number,
+        },
+        platform_fee,
+        payouts,
+    };
+
+    initialize_pda_account(
+        purchaser_account,
+        receipt_account,
+        system_program,
+        program_id,
+        &purchase,
+        &[
+            RECEIPT_SEED,
+            chapter_account.key.as_ref(),
+            purchaser_user_account.key.as_ref(),
+            &[receipt_bump],
+        ],
+    )?;
+
+    record_sale(content_account, &mut content, amount)?;
+
+    msg!("Chapter purchased successfully: {:?}", purchase);
+
+    Ok(())
+}
+
+// Pay the platform, every contributor and the creator for one sale of
+// `content`, reading the token and recipient accounts that follow the fixed
+// ones. Returns the platform fee and the payouts to record on the receipt.
+#[allow(clippy::too_many_arguments)]
+fn collect_payment<'a, 'b>(
+    account_info_iter: &mut impl Iterator<Item = &'b AccountInfo<'a>>,
+    content: &MonetizedContent,
+    config: &PlatformConfig,
+    purchaser_account: &'b AccountInfo<'a>,
+    creator_account: &'b AccountInfo<'a>,
+    treasury_account: &'b AccountInfo<'a>,
+    system_program: &'b AccountInfo<'a>,
+    amount: u64,
+) -> Result<(u64, Vec<Payout>), ProgramError>
+where
+    'a: 'b,
+{
+    // Resolve how funds move, and where the creator's part lands
+    let (route, creator_destination) = match content.payment_mint {
+        None => (PaymentRoute::Sol { system_program }, creator_account),
+        Some(payment_mint) => {
+            let purchaser_token_account = next_account_info(account_info_iter)?;
+            let creator_token_account = next_account_info(account_info_iter)?;
+            let mint_account = next_account_info(account_info_iter)?;
+            let token_program = next_account_info(account_info_iter)?;
+
+            if *mint_account.key != payment_mint {
+                msg!("Mint does not match the content's payment mint!");
+                return Err(AnimeNexaError::PaymentMintMismatch.into());
+            }
+
+            let route = PaymentRoute::Token {
+                source: purchaser_token_account,
+                mint: mint_account,
+                token_program,
+            };
+            (route, creator_token_account)
+        }
+    };
+
+    let split = split_amount(amount, config.fee_basis_points, &content.revenue_shares)?;
+
+    // Pay the platform, every contributor and the creator in one instruction
+    route.pay(
+        purchaser_account,
+        treasury_account,
+        &config.treasury,
+        split.platform_fee,
+    )?;
+
+    let mut payouts = Vec::with_capacity(content.revenue_shares.len() + 1);
+    for (share, share_amount) in content.revenue_shares.iter().zip(&split.share_amounts) {
+        let recipient_account = next_account_info(account_info_iter).map_err(|_| {
+            msg!("Missing recipient account for a revenue share!");
+            ProgramError::from(AnimeNexaError::RevenueShareMismatch)
+        })?;
+        route.pay(
+            purchaser_account,
+            recipient_account,
+            &share.wallet,
+            *share_amount,
+        )?;
+        payouts.push(Payout {
+            wallet: share.wallet,
+            amount: *share_amount,
+        });
+    }
+
+    route.pay(
+        purchaser_account,
+        creator_destination,
+        creator_account.key,
+        split.creator_amount,
+    )?;
+    payouts.push(Payout {
+        wallet: *creator_account.key,
+        amount: split.creator_amount,
+    });
+
+    Ok((split.platform_fee, payouts))
+}
+
+// Add one sale of `amount` to the content's counters
+fn record_sale(
+    content_account: &AccountInfo,
+    content: &mut MonetizedContent,
+    amount: u64,
+) -> ProgramResult {
+    content.total_sales = content
+        .total_sales
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    content.total_revenue = content
+        .total_revenue
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    write_in_place(content_account, content)?;
+    Ok(())
+}
+
+/// Load monetized content, checking it is the PDA created for `creator_account`.
+pub fn load_content(
+    program_id: &Pubkey,
+    creator_account: &AccountInfo,
+    content_account: &AccountInfo,
+) -> Result<MonetizedContent, ProgramError> {
+    if content_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let content = MonetizedContent::try_from_slice(&content_account.data.borrow())?;
+
+    let (content_address, _) =
+        find_content_address(program_id, creator_account.key, &content.content_id);
+    if *content_account.key != content_address {
+        msg!("Content account was not created by the creator!");
+        return Err(AnimeNexaError::NotCreator.into());
+    }
+
+    Ok(content)
+}
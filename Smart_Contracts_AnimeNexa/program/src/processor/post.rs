@@ -0,0 +1,504 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    hash::hash,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::AnimeNexaError,
+    event::PostEvent,
+    pda::{
+        find_media_address, find_post_address, find_repost_address, find_username_address,
+        is_valid_username, normalize_username, POST_SEED, REPOST_SEED,
+    },
+    state::{
+        post::{
+            is_valid_hashtag, MAX_CONTENT_WARNINGS, MAX_HASHTAGS, MAX_MENTIONS,
+            MAX_POST_MEDIA_REFS, MAX_POST_TEXT_LEN,
+        },
+        MediaReference, Post, PostContent, PostVisibility, ReactionCounts, RepostRecord,
+        UsernameRecord,
+    },
+    utils::{
+        authorize_user, check_id, check_new_account, close_account, initialize_pda_account,
+        update_account, write_in_place,
+    },
+};
+
+pub fn create_post(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    post_id: String,
+    user_id: String,
+    content: PostContent,
+    timestamp: u64,
+    visibility: PostVisibility,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let post_account = next_account_info(account_info_iter)?;
+    let payer_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let user = authorize_user(program_id, user_account, wallet_account)?;
+
+    check_new_account(program_id, payer_account, post_account, system_program)?;
+    check_id(&post_id)?;
+    check_post_content(&content)?;
+
+    // Validate that the user exists
+    if user.user_id != user_id {
+        msg!("Invalid user ID!");
+        return Err(AnimeNexaError::UserIdMismatch.into());
+    }
+
+    let (post_address, bump) = find_post_address(program_id, user_account.key, &post_id);
+    if *post_account.key != post_address {
+        msg!("Post account does not match the post ID!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let events = content_events(
+        program_id,
+        account_info_iter,
+        post_account,
+        user_account,
+        &content,
+    )?;
+
+    // Create the post
+    let post = Post {
+        post_id,
+        user_id,
+        content,
+        timestamp,
+        media_ids: vec![],
+        revision: 0,
+        previous_content_hash: None,
+        deleted: false,
+        comment_count: 0,
+        reactions: ReactionCounts::default(),
+        visibility,
+        repost_of: None,
+        repost_count: 0,
+    };
+
+    initialize_pda_account(
+        payer_account,
+        post_account,
+        system_program,
+        program_id,
+        &post,
+        &[
+            POST_SEED,
+            user_account.key.as_ref(),
+            post.post_id.as_bytes(),
+            &[bump],
+        ],
+    )?;
+
+    events.iter().try_for_each(PostEvent::emit)?;
+
+    msg!("Post created successfully: {:?}", post);
+
+    Ok(())
+}
+
+// Share an existing post as a new post of the user's own. With `quote` it is a
+// quote post carrying the user's commentary; without, the content stays empty.
+// A deleted original is left as a tombstone, so reposts keep pointing at it and
+// clients can show it as removed.
+pub fn repost(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    post_id: String,
+    user_id: String,
+    quote: Option<PostContent>,
+    timestamp: u64,
+    visibility: PostVisibility,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let post_account = next_account_info(account_info_iter)?;
+    let original_account = next_account_info(account_info_iter)?;
+    let original_author_account = next_account_info(account_info_iter)?;
+    let record_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let user = authorize_user(program_id, user_account, wallet_account)?;
+    if user.user_id != user_id {
+        msg!("Invalid user ID!");
+        return Err(AnimeNexaError::UserIdMismatch.into());
+    }
+
+    check_new_account(program_id, wallet_account, post_account, system_program)?;
+    check_id(&post_id)?;
+
+    let content = quote.unwrap_or_default();
+    check_post_content(&content)?;
+
+    let mut original = load_post(program_id, original_author_account, original_account)?;
+    if original.deleted {
+        msg!("Original post has been deleted!");
+        return Err(AnimeNexaError::PostDeleted.into());
+    }
+
+    let (post_address, bump) = find_post_address(program_id, user_account.key, &post_id);
+    if *post_account.key != post_address {
+        msg!("Post account does not match the post ID!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let (record_address, record_bump) =
+        find_repost_address(program_id, original_account.key, user_account.key);
+    if *record_account.key != record_address {
+        msg!("Repost record does not match the original post!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let events = content_events(
+        program_id,
+        account_info_iter,
+        post_account,
+        user_account,
+        &content,
+    )?;
+
+    let post = Post {
+        post_id,
+        user_id,
+        content,
+        timestamp,
+        media_ids: vec![],
+        revision: 0,
+        previous_content_hash: None,
+        deleted: false,
+        comment_count: 0,
+        reactions: ReactionCounts::default(),
+        // Reposting cannot widen who the original was shared with
+        visibility: visibility.at_most(original.visibility),
+        repost_of: Some(*original_account.key),
+        repost_count: 0,
+    };
+
+    initialize_pda_account(
+        wallet_account,
+        post_account,
+        system_program,
+        program_id,
+        &post,
+        &[
+            POST_SEED,
+            user_account.key.as_ref(),
+            post.post_id.as_bytes(),
+            &[bump],
+        ],
+    )?;
+
+    // Only the user's first live repost of the original counts towards it
+    if record_account.owner == program_id {
+        let mut record = RepostRecord::try_from_slice(&record_account.data.borrow())?;
+        record.reposts = record
+            .reposts
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        write_in_place(record_account, &record)?;
+    } else {
+        let record = RepostRecord {
+            user: *user_account.key,
+            original: *original_account.key,
+            reposts: 1,
+        };
+        initialize_pda_account(
+            wallet_account,
+            record_account,
+            system_program,
+            program_id,
+            &record,
+            &[
+                REPOST_SEED,
+                original_account.key.as_ref(),
+                user_account.key.as_ref(),
+                &[record_bump],
+            ],
+        )?;
+
+        original.repost_count = original
+            .repost_count
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        write_in_place(original_account, &original)?;
+    }
+
+    events.iter().try_for_each(PostEvent::emit)?;
+
+    msg!("Post reposted successfully: {:?}", post);
+
+    Ok(())
+}
+
+// Replace the content of one of the author's posts, keeping a hash of the old one
+pub fn edit_post(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    post_id: String,
+    content: PostContent,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let post_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let mut post = load_author_post(
+        program_id,
+        user_account,
+        wallet_account,
+        post_account,
+        &post_id,
+    )?;
+    check_post_content(&content)?;
+    let events = content_events(
+        program_id,
+        account_info_iter,
+        post_account,
+        user_account,
+        &content,
+    )?;
+
+    post.previous_content_hash = Some(hash(&borsh::to_vec(&post.content)?).to_bytes());
+    post.revision = post
+        .revision
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    post.content = content;
+
+    // Serialize the post data back to the account
+    update_account(post_account, wallet_account, system_program, &post)?;
+
+    events.iter().try_for_each(PostEvent::emit)?;
+
+    msg!("Post edited successfully: {:?}", post);
+
+    Ok(())
+}
+
+// Turn one of the author's posts into a tombstone, closing its media and
+// refunding the freed rent
+pub fn delete_post(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    post_id: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let post_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let mut post = load_author_post(
+        program_id,
+        user_account,
+        wallet_account,
+        post_account,
+        &post_id,
+    )?;
+
+    // The account stays behind so the post ID cannot be reused, and so
+    // anything referring to the post can tell it was deleted
+    post.content = PostContent::default();
+    let media_ids = std::mem::take(&mut post.media_ids);
+    post.previous_content_hash = None;
+    post.deleted = true;
+
+    // Shrinking the account returns the surplus rent to the author's wallet
+    update_account(post_account, wallet_account, system_program, &post)?;
+
+    // The user's last live repost no longer counts towards its original, which
+    // may itself have been deleted since
+    if let Some(original_address) = post.repost_of {
+        let original_account = next_account_info(account_info_iter)?;
+        if *original_account.key != original_address || original_account.owner != program_id {
+            msg!("Original post account does not match the repost!");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let record_account = next_account_info(account_info_iter)?;
+        let (record_address, _) =
+            find_repost_address(program_id, &original_address, user_account.key);
+        if *record_account.key != record_address || record_account.owner != program_id {
+            msg!("Repost record does not match the repost!");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut record = RepostRecord::try_from_slice(&record_account.data.borrow())?;
+        record.reposts = record.reposts.saturating_sub(1);
+        if record.reposts == 0 {
+            close_account(record_account, wallet_account)?;
+
+            let mut original = Post::try_from_slice(&original_account.data.borrow())?;
+            original.repost_count = original.repost_count.saturating_sub(1);
+            write_in_place(original_account, &original)?;
+        } else {
+            write_in_place(record_account, &record)?;
+        }
+    }
+
+    // Close the attached media so none is left pointing at the deleted post
+    for media_id in &media_ids {
+        let media_account = next_account_info(account_info_iter)?;
+        let (media_address, _) = find_media_address(program_id, user_account.key, media_id);
+        if *media_account.key != media_address || media_account.owner != program_id {
+            msg!("Media account does not match media {}!", media_id);
+            return Err(ProgramError::InvalidArgument);
+        }
+        close_account(media_account, wallet_account)?;
+    }
+
+    msg!("Post deleted successfully: {}", post.post_id);
+
+    Ok(())
+}
+
+// Authorize the author and load the live post they are changing
+fn load_author_post(
+    program_id: &Pubkey,
+    user_account: &AccountInfo,
+    wallet_account: &AccountInfo,
+    post_account: &AccountInfo,
+    post_id: &str,
+) -> Result<Post, ProgramError> {
+    authorize_user(program_id, user_account, wallet_account)?;
+
+    // The post PDA is derived from the author's user account
+    let post = load_post(program_id, user_account, post_account)?;
+    if post.post_id != post_id {
+        msg!("Post ID does not match!");
+        return Err(AnimeNexaError::PostIdMismatch.into());
+    }
+    if post.deleted {
+        msg!("Post has been deleted!");
+        return Err(AnimeNexaError::PostDeleted.into());
+    }
+
+    Ok(post)
+}
+
+// Check every part of a post's content is within its limits
+pub fn check_post_content(content: &PostContent) -> ProgramResult {
+    if content.text.len() > MAX_POST_TEXT_LEN {
+        msg!("Post text is too long!");
+        return Err(AnimeNexaError::TextTooLong.into());
+    }
+
+    if !content.media.iter().all(MediaReference::is_valid) {
+        msg!("Invalid media reference in post!");
+        return Err(AnimeNexaError::InvalidMediaReference.into());
+    }
+
+    let mentions_valid = content
+        .mentions
+        .iter()
+        .all(|mention| is_valid_username(&normalize_username(mention)));
+    let hashtags_valid = content
+        .hashtags
+        .iter()
+        .all(|hashtag| is_valid_hashtag(hashtag));
+    let warnings_unique = content
+        .content_warnings
+        .iter()
+        .enumerate()
+        .all(|(i, warning)| !content.content_warnings[..i].contains(warning));
+
+    if content.media.len() > MAX_POST_MEDIA_REFS
+        || content.mentions.len() > MAX_MENTIONS
+        || content.hashtags.len() > MAX_HASHTAGS
+        || content.content_warnings.len() > MAX_CONTENT_WARNINGS
+        || !mentions_valid
+        || !hashtags_valid
+        || !warnings_unique
+    {
+        msg!("Invalid post content!");
+        return Err(AnimeNexaError::InvalidPostContent.into());
+    }
+
+    Ok(())
+}
+
+// Check every mention against the username registry, whose entries follow the
+// instruction's other accounts in mention order, and build the events logged
+// for each hashtag and mention of the post once it is stored
+fn content_events<'a, 'b>(
+    program_id: &Pubkey,
+    account_info_iter: &mut impl Iterator<Item = &'b AccountInfo<'a>>,
+    post_account: &AccountInfo,
+    author_account: &AccountInfo,
+    content: &PostContent,
+) -> Result<Vec<PostEvent>, ProgramError>
+where
+    'a: 'b,
+{
+    let mut events = Vec::with_capacity(content.hashtags.len() + content.mentions.len());
+
+    for mention in &content.mentions {
+        let record_account = next_account_info(account_info_iter).map_err(|_| {
+            msg!("Missing username registry account for @{}!", mention);
+            ProgramError::NotEnoughAccountKeys
+        })?;
+
+        let username = normalize_username(mention);
+        let (record_address, _) = find_username_address(program_id, &username);
+        if *record_account.key != record_address {
+            msg!("Username registry account does not match @{}!", mention);
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if record_account.owner != program_id {
+            msg!("Username @{} is not registered!", mention);
+            return Err(AnimeNexaError::UnknownMention.into());
+        }
+
+        let record = UsernameRecord::try_from_slice(&record_account.data.borrow())?;
+        events.push(PostEvent::Mention {
+            post: *post_account.key,
+            author: *author_account.key,
+            mentioned_user: record.user_account,
+            username: record.username,
+        });
+    }
+
+    events.extend(content.hashtags.iter().map(|hashtag| PostEvent::Hashtag {
+        post: *post_account.key,
+        author: *author_account.key,
+        hashtag: hashtag.clone(),
+    }));
+
+    Ok(events)
+}
+
+/// Load a post, checking it is the PDA created for `author_account`.
+pub fn load_post(
+    program_id: &Pubkey,
+    author_account: &AccountInfo,
+    post_account: &AccountInfo,
+) -> Result<Post, ProgramError> {
+    if post_account.owner != program_id {
+        msg!("Post does not exist!");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let post = Post::try_from_slice(&post_account.data.borrow())?;
+
+    let (post_address, _) = find_post_address(program_id, author_account.key, &post.post_id);
+    if *post_account.key != post_address {
+        msg!("Post account was not created by the author!");
+        return Err(AnimeNexaError::Unauthorized.into());
+    }
+
+    Ok(post)
+}
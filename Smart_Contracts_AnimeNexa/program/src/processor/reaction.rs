@@ -0,0 +1,211 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{clock::Clock, Sysvar},
+};
+
+use crate::{
+    error::AnimeNexaError,
+    pda::{find_reaction_address, REACTION_SEED},
+    processor::{comment::load_comment, manga::load_manga, post::load_post},
+    state::{Reaction, ReactionCounts, ReactionKind, ReactionTarget},
+    utils::{
+        authorize_user, check_new_account, close_account, initialize_pda_account, write_in_place,
+    },
+};
+
+// React to a post, manga or comment. Each user holds at most one reaction per
+// target; reacting again switches its kind.
+pub fn react(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    user_id: String,
+    target_kind: ReactionTarget,
+    kind: ReactionKind,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let target_account = next_account_info(account_info_iter)?;
+    let target_author_account = next_account_info(account_info_iter)?;
+    let reaction_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let user = authorize_user(program_id, user_account, wallet_account)?;
+    if user.user_id != user_id {
+        msg!("Invalid user ID!");
+        return Err(AnimeNexaError::UserIdMismatch.into());
+    }
+
+    let (reaction_address, bump) =
+        find_reaction_address(program_id, target_account.key, user_account.key);
+    if *reaction_account.key != reaction_address {
+        msg!("Reaction account does not match the target!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // Switch the kind of an existing reaction in place
+    if reaction_account.owner == program_id {
+        let mut reaction = Reaction::try_from_slice(&reaction_account.data.borrow())?;
+        if reaction.target_kind != target_kind {
+            msg!("Reaction was left on another kind of target!");
+            return Err(AnimeNexaError::ReactionMismatch.into());
+        }
+        if reaction.kind == kind {
+            msg!("Reaction is unchanged: {:?}", reaction);
+            return Ok(());
+        }
+
+        apply_reaction(
+            program_id,
+            target_kind,
+            target_account,
+            target_author_account,
+            Some(reaction.kind),
+            Some(kind),
+        )?;
+
+        reaction.kind = kind;
+        reaction.reacted_at = now;
+        write_in_place(reaction_account, &reaction)?;
+
+        msg!("Reaction updated successfully: {:?}", reaction);
+
+        return Ok(());
+    }
+
+    check_new_account(program_id, wallet_account, reaction_account, system_program)?;
+
+    apply_reaction(
+        program_id,
+        target_kind,
+        target_account,
+        target_author_account,
+        None,
+        Some(kind),
+    )?;
+
+    let reaction = Reaction {
+        user_id,
+        user: *user_account.key,
+        target: *target_account.key,
+        target_kind,
+        kind,
+        reacted_at: now,
+    };
+
+    initialize_pda_account(
+        wallet_account,
+        reaction_account,
+        system_program,
+        program_id,
+        &reaction,
+        &[
+            REACTION_SEED,
+            target_account.key.as_ref(),
+            user_account.key.as_ref(),
+            &[bump],
+        ],
+    )?;
+
+    msg!("Reaction added successfully: {:?}", reaction);
+
+    Ok(())
+}
+
+// Remove the user's reaction from a target, refunding its rent to the wallet
+pub fn unreact(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let target_account = next_account_info(account_info_iter)?;
+    let target_author_account = next_account_info(account_info_iter)?;
+    let reaction_account = next_account_info(account_info_iter)?;
+
+    authorize_user(program_id, user_account, wallet_account)?;
+
+    if reaction_account.owner != program_id {
+        msg!("No reaction to remove!");
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let (reaction_address, _) =
+        find_reaction_address(program_id, target_account.key, user_account.key);
+    if *reaction_account.key != reaction_address {
+        msg!("Reaction account does not match the target!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let reaction = Reaction::try_from_slice(&reaction_account.data.borrow())?;
+
+    apply_reaction(
+        program_id,
+        reaction.target_kind,
+        target_account,
+        target_author_account,
+        Some(reaction.kind),
+        None,
+    )?;
+
+    close_account(reaction_account, wallet_account)?;
+
+    msg!("Reaction removed successfully: {:?}", reaction);
+
+    Ok(())
+}
+
+// Move the target's counters from the `removed` reaction kind to the `added`
+// one. Reactions can be taken back from deleted posts and comments, but not
+// left on them.
+fn apply_reaction(
+    program_id: &Pubkey,
+    target_kind: ReactionTarget,
+    target_account: &AccountInfo,
+    target_author_account: &AccountInfo,
+    removed: Option<ReactionKind>,
+    added: Option<ReactionKind>,
+) -> ProgramResult {
+    let update = |counts: &mut ReactionCounts| -> ProgramResult {
+        if let Some(kind) = removed {
+            counts.remove(kind);
+        }
+        if let Some(kind) = added {
+            counts.add(kind).ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+        Ok(())
+    };
+
+    match target_kind {
+        ReactionTarget::Post => {
+            let mut post = load_post(program_id, target_author_account, target_account)?;
+            if post.deleted && added.is_some() {
+                msg!("Post has been deleted!");
+                return Err(AnimeNexaError::PostDeleted.into());
+            }
+            update(&mut post.reactions)?;
+            write_in_place(target_account, &post)?;
+        }
+        ReactionTarget::Manga => {
+            let mut manga = load_manga(program_id, target_author_account, target_account)?;
+            update(&mut manga.reactions)?;
+            write_in_place(target_account, &manga)?;
+        }
+        ReactionTarget::Comment => {
+            let mut comment = load_comment(program_id, target_account)?;
+            if comment.deleted && added.is_some() {
+                msg!("Comment has been deleted!");
+                return Err(AnimeNexaError::CommentDeleted.into());
+            }
+            update(&mut comment.reactions)?;
+            write_in_place(target_account, &comment)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::AnimeNexaError,
+    payment::transfer_sol,
+    pda::{find_support_address, SUPPORT_SEED},
+    state::{support::MAX_SUPPORT_MESSAGE_LEN, Support},
+    utils::{
+        authorize_user, check_id, check_new_account, initialize_pda_account, load_user,
+        write_in_place,
+    },
+};
+
+// Implementing the Support User Logic
+#[allow(clippy::too_many_arguments)]
+pub fn support_user(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    support_id: String,
+    sender_user_id: String,
+    recipient_user_id: String,
+    amount: u64,
+    timestamp: u64,
+    message: Option<String>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let sender_account = next_account_info(account_info_iter)?;
+    let wallet_account = next_account_info(account_info_iter)?;
+    let recipient_account = next_account_info(account_info_iter)?;
+    let recipient_wallet_account = next_account_info(account_info_iter)?;
+    let support_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let sender = authorize_user(program_id, sender_account, wallet_account)?;
+    if sender.user_id != sender_user_id {
+        msg!("Invalid sender user ID!");
+        return Err(AnimeNexaError::UserIdMismatch.into());
+    }
+
+    // A self-tip would only inflate the lifetime totals
+    if sender_account.key == recipient_account.key {
+        msg!("User cannot tip themselves!");
+        return Err(AnimeNexaError::CannotTipSelf.into());
+    }
+
+    let mut recipient = load_user(program_id, recipient_account)?;
+    if recipient.user_id != recipient_user_id {
+        msg!("Invalid recipient user ID!");
+        return Err(AnimeNexaError::UserIdMismatch.into());
+    }
+    if recipient.public_key != *recipient_wallet_account.key {
+        msg!("Tip must go to the recipient's wallet!");
+        return Err(AnimeNexaError::WalletMismatch.into());
+    }
+
+    check_new_account(program_id, wallet_account, support_account, system_program)?;
+    check_id(&support_id)?;
+
+    if amount == 0 {
+        msg!("Tip amount must be greater than zero!");
+        return Err(AnimeNexaError::InvalidAmount.into());
+    }
+
+    if message
+        .as_ref()
+        .is_some_and(|message| message.len() > MAX_SUPPORT_MESSAGE_LEN)
+    {
+        msg!("Support message is too long!");
+        return Err(AnimeNexaError::TextTooLong.into());
+    }
+
+    let (support_address, bump) = find_support_address(program_id, sender_account.key, &support_id);
+    if *support_account.key != support_address {
+        msg!("Support account does not match the support ID!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Transfer the tip from sender to recipient
+    transfer_sol(
+        wallet_account,
+        recipient_wallet_account,
+        system_program,
+        amount,
+    )?;
+
+    let support = Support {
+        support_id,
+        sender_user_id,
+        sender: *sender_account.key,
+        recipient_user_id,
+        recipient: *recipient_account.key,
+        amount,
+        message,
+        timestamp,
+    };
+
+    initialize_pda_account(
+        wallet_account,
+        support_account,
+        system_program,
+        program_id,
+        &support,
+        &[
+            SUPPORT_SEED,
+            sender_account.key.as_ref(),
+            support.support_id.as_bytes(),
+            &[bump],
+        ],
+    )?;
+
+    // Update the recipient's lifetime totals
+    recipient.tips_received_total = recipient
+        .tips_received_total
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    recipient.tips_received_count = recipient
+        .tips_received_count
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    write_in_place(recipient_account, &recipient)?;
+
+    msg!("User supported successfully: {:?}", support);
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::{
+    error::AnimeNexaError,
+    pda::{
+        find_user_address, find_username_address, is_valid_username, normalize_username,
+        USERNAME_SEED, USER_SEED,
+    },
+    state::{User, UsernameRecord},
+    utils::initialize_pda_account,
+};
+
+pub fn register_user(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    user_id: String,
+    username: String,
+    profile_data: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let wallet_account = next_account_info(account_info_iter)?;
+    let user_account = next_account_info(account_info_iter)?;
+    let username_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    if !wallet_account.is_signer {
+        msg!("Wallet must sign the registration!");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if *system_program.key != system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let normalized = normalize_username(&username);
+    if !is_valid_username(&normalized) {
+        msg!("Invalid username!");
+        return Err(AnimeNexaError::InvalidUsername.into());
+    }
+
+    // Both accounts are derived, so a caller cannot substitute arbitrary ones
+    let (user_address, user_bump) = find_user_address(program_id, wallet_account.key);
+    if *user_account.key != user_address {
+        msg!("User account does not match the wallet!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let (username_address, username_bump) = find_username_address(program_id, &normalized);
+    if *username_account.key != username_address {
+        msg!("Username account does not match the username!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Validate unique username
+    if username_account.owner == program_id {
+        msg!("Username already exists!");
+        return Err(AnimeNexaError::UsernameTaken.into());
+    }
+
+    if user_account.owner == program_id {
+        msg!("Wallet is already registered!");
+        return Err(AnimeNexaError::UserAlreadyRegistered.into());
+    }
+
+    // Register the user under the name its registry entry reserves, keeping
+    // its case for display
+    let user = User {
+        user_id,
+        username: username.trim().to_string(),
+        public_key: *wallet_account.key,
+        profile_data,
+        tips_received_total: 0,
+        tips_received_count: 0,
+        follower_count: 0,
+        following_count: 0,
+    };
+    let record = UsernameRecord {
+        username: normalized,
+        owner: *wallet_account.key,
+        user_account: *user_account.key,
+    };
+
+    // Create the registry entry and the user account in the same instruction,
+    // so either both exist or neither does
+    initialize_pda_account(
+        wallet_account,
+        username_account,
+        system_program,
+        program_id,
+        &record,
+        &[USERNAME_SEED, record.username.as_bytes(), &[username_bump]],
+    )?;
+    initialize_pda_account(
+        wallet_account,
+        user_account,
+        system_program,
+        program_id,
+        &user,
+        &[USER_SEED, wallet_account.key.as_ref(), &[user_bump]],
+    )?;
+
+    msg!("User registered successfully: {:?}", user);
+
+    Ok(())
+}
+
+pub fn authenticate_user(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    user_id: String,
+    public_key: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let user_account = next_account_info(account_info_iter)?;
+
+    if user_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let user = User::try_from_slice(&user_account.data.borrow())?;
+
+    // Check if the user ID matches
+    if user.user_id != user_id {
+        msg!("User ID does not match!");
+        return Err(AnimeNexaError::UserIdMismatch.into());
+    }
+
+    // Authenticate by verifying the public key
+    if user.public_key != public_key {
+        msg!("Public key does not match!");
+        return Err(AnimeNexaError::PublicKeyMismatch.into());
+    }
+
+    msg!("User authenticated successfully: {:?}", user);
+
+    Ok(())
+}
@@ -0,0 +1,333 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program::invoke_signed,
+    program_error::ProgramError, pubkey::Pubkey, rent::Rent, system_instruction, sysvar::Sysvar,
+};
+
+use crate::{
+    error::AnimeNexaError,
+    pda::{find_config_address, find_user_address, MAX_ID_LEN},
+    state::{MediaReference, MediaType, PlatformConfig, User},
+};
+
+/// Create a program-owned account at a PDA, funded rent-exempt by `payer`.
+///
+/// A PDA that was pre-funded by someone else cannot go through
+/// `create_account`, so in that case the missing lamports are topped up and the
+/// account is allocated and assigned separately.
+pub fn create_pda_account<'a>(
+    payer: &AccountInfo<'a>,
+    new_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    program_id: &Pubkey,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let rent = Rent::get()?;
+    let required_lamports = rent.minimum_balance(space);
+
+    if new_account.lamports() == 0 {
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                new_account.key,
+                required_lamports,
+                space as u64,
+                program_id,
+            ),
+            &[payer.clone(), new_account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )
+    } else {
+        let missing_lamports = required_lamports.saturating_sub(new_account.lamports());
+        if missing_lamports > 0 {
+            invoke_signed(
+                &system_instruction::transfer(payer.key, new_account.key, missing_lamports),
+                &[payer.clone(), new_account.clone(), system_program.clone()],
+                &[],
+            )?;
+        }
+        invoke_signed(
+            &system_instruction::allocate(new_account.key, space as u64),
+            &[new_account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(new_account.key, program_id),
+            &[new_account.clone(), system_program.clone()],
+            &[signer_seeds],
+        )
+    }
+}
+
+/// Create a PDA sized to exactly fit the borsh encoding of `state` and store it.
+pub fn initialize_pda_account<'a, T: BorshSerialize>(
+    payer: &AccountInfo<'a>,
+    new_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    program_id: &Pubkey,
+    state: &T,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let data = borsh::to_vec(state)?;
+    create_pda_account(
+        payer,
+        new_account,
+        system_program,
+        program_id,
+        data.len(),
+        signer_seeds,
+    )?;
+    new_account.data.borrow_mut().copy_from_slice(&data);
+    Ok(())
+}
+
+// Check the accounts shared by every instruction that creates a new record
+pub fn check_new_account(
+    program_id: &Pubkey,
+    payer: &AccountInfo,
+    new_account: &AccountInfo,
+    system_program: &AccountInfo,
+) -> ProgramResult {
+    if !payer.is_signer {
+        msg!("Payer must sign!");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if *system_program.key != solana_program::system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if new_account.owner == program_id {
+        msg!("Account is already initialized!");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    Ok(())
+}
+
+// Check that a record ID fits in a single PDA seed
+pub fn check_id(id: &str) -> ProgramResult {
+    if id.is_empty() || id.len() > MAX_ID_LEN {
+        msg!("ID must be between 1 and {} bytes!", MAX_ID_LEN);
+        return Err(AnimeNexaError::InvalidId.into());
+    }
+    Ok(())
+}
+
+/// Load the user stored in `user_account` and require the wallet recorded in
+/// `User.public_key` to have signed the transaction.
+///
+/// Every instruction acting on behalf of a user goes through this check, so a
+/// caller cannot act as someone else just by passing their user account.
+pub fn authorize_user(
+    program_id: &Pubkey,
+    user_account: &AccountInfo,
+    wallet_account: &AccountInfo,
+) -> Result<User, ProgramError> {
+    if !wallet_account.is_signer {
+        msg!("User wallet must sign!");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let user = load_user(program_id, user_account)?;
+
+    if user.public_key != *wallet_account.key {
+        msg!("Signer does not own the user account!");
+        return Err(AnimeNexaError::Unauthorized.into());
+    }
+
+    Ok(user)
+}
+
+/// Load a genuine user account, i.e. a program-owned account sitting at the
+/// PDA derived from the wallet it records.
+pub fn load_user(program_id: &Pubkey, user_account: &AccountInfo) -> Result<User, ProgramError> {
+    if user_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let user = User::try_from_slice(&user_account.data.borrow())?;
+
+    let (user_address, _) = find_user_address(program_id, &user.public_key);
+    if *user_account.key != user_address {
+        msg!("User account does not match the wallet!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(user)
+}
+
+// Check a media reference is well-formed and fits the constraints of `media_type`
+pub fn check_media(reference: &MediaReference, media_type: MediaType) -> ProgramResult {
+    if !reference.is_valid() {
+        msg!("Invalid media reference!");
+        return Err(AnimeNexaError::InvalidMediaReference.into());
+    }
+
+    if !media_type.accepts_mime_type(&reference.mime_type) {
+        msg!(
+            "MIME type {} is not supported for {:?}!",
+            reference.mime_type,
+            media_type
+        );
+        return Err(AnimeNexaError::UnsupportedMediaType.into());
+    }
+
+    if !media_type.within_limits(reference.byte_size) {
+        msg!("Media exceeds the limits for {:?}!", media_type);
+        return Err(AnimeNexaError::MediaTooLarge.into());
+    }
+
+    Ok(())
+}
+
+// Load the global platform config
+pub fn load_config(
+    program_id: &Pubkey,
+    config_account: &AccountInfo,
+) -> Result<PlatformConfig, ProgramError> {
+    if config_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if *config_account.key != find_config_address(program_id).0 {
+        msg!("Invalid platform config account!");
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(PlatformConfig::try_from_slice(
+        &config_account.data.borrow(),
+    )?)
+}
+
+/// Rewrite a program-owned account with `state`, resizing it when the encoded
+/// length changed. `payer` covers the extra rent when growing and receives the
+/// surplus when shrinking.
+pub fn update_account<'a, T: BorshSerialize>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    state: &T,
+) -> ProgramResult {
+    let data = borsh::to_vec(state)?;
+
+    if data.len() != account.data_len() {
+        let required_lamports = Rent::get()?.minimum_balance(data.len());
+        let current_lamports = account.lamports();
+
+        if required_lamports > current_lamports {
+            pay_rent(
+                account,
+                payer,
+                system_program,
+                required_lamports - current_lamports,
+            )?;
+        } else if current_lamports > required_lamports {
+            let surplus = current_lamports - required_lamports;
+            **account.try_borrow_mut_lamports()? -= surplus;
+            **payer.try_borrow_mut_lamports()? += surplus;
+        }
+
+        account.realloc(data.len(), false)?;
+    }
+
+    account.data.borrow_mut().copy_from_slice(&data);
+    Ok(())
+}
+
+/// Overwrite `account` with `state` without resizing it, for updates that only
+/// change fixed-size fields such as counters, roles and keys.
+///
+/// The encoding must fill the account exactly: a shorter one would leave
+/// trailing bytes behind, and the next `try_from_slice` would reject them.
+pub fn write_in_place<T: BorshSerialize>(account: &AccountInfo, state: &T) -> ProgramResult {
+    let data = borsh::to_vec(state)?;
+    if data.len() != account.data_len() {
+        msg!("Account data changed length!");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    account.data.borrow_mut().copy_from_slice(&data);
+    Ok(())
+}
+
+/// Store `state` in an account that can only grow. `payer` only covers the
+/// rent the account's lamports fall short of, so rent kept by
+/// [`shrink_account`] is used first and never paid out.
+pub fn grow_account<'a, T: BorshSerialize>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    state: &T,
+) -> ProgramResult {
+    let data = borsh::to_vec(state)?;
+    if data.len() < account.data_len() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let required_lamports = Rent::get()?.minimum_balance(data.len());
+    let current_lamports = account.lamports();
+    if required_lamports > current_lamports {
+        pay_rent(
+            account,
+            payer,
+            system_program,
+            required_lamports - current_lamports,
+        )?;
+    }
+
+    account.realloc(data.len(), false)?;
+    account.data.borrow_mut().copy_from_slice(&data);
+    Ok(())
+}
+
+// Transfer `lamports` of rent from `payer` to `account` through the system program
+fn pay_rent<'a>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    lamports: u64,
+) -> ProgramResult {
+    if *system_program.key != solana_program::system_program::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    invoke_signed(
+        &system_instruction::transfer(payer.key, account.key, lamports),
+        &[payer.clone(), account.clone(), system_program.clone()],
+        &[],
+    )
+}
+
+/// Store `state` in an account that can only shrink, keeping the rent it no
+/// longer needs in the account so it funds later growth.
+pub fn shrink_account<T: BorshSerialize>(account: &AccountInfo, state: &T) -> ProgramResult {
+    let data = borsh::to_vec(state)?;
+    if data.len() > account.data_len() {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+
+    account.realloc(data.len(), false)?;
+    account.data.borrow_mut().copy_from_slice(&data);
+    Ok(())
+}
+
+/// Close a program-owned account, moving all of its lamports to `destination`.
+///
+/// The data is cleared and ownership returns to the system program, so the
+/// address can be initialized again later.
+pub fn close_account<'a>(
+    account: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+) -> ProgramResult {
+    let lamports = account.lamports();
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+
+    account.realloc(0, false)?;
+    account.assign(&solana_program::system_program::id());
+    Ok(())
+}
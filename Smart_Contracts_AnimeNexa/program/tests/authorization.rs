@@ -0,0 +1,496 @@
+//! Every instruction acting for a user must be signed by that user's wallet.
+//!
+//! These tests drive the processor directly with hand-built accounts, see
+//! `common` for the runtime they run in.
+
+mod common;
+
+use animenexa_program::{
+    error::AnimeNexaError,
+    instruction::UserInstruction,
+    pda::find_content_address,
+    state::{
+        AccessTerms, GroupRole, MediaLocator, MediaReference, MediaType, MonetizedContent,
+        PostContent, PostVisibility, ReactionKind, ReactionTarget,
+    },
+    utils::authorize_user,
+};
+use common::{group_account, run, system_program_account, user_account, Fixture, TestAccount};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+fn unauthorized() -> ProgramError {
+    AnimeNexaError::Unauthorized.into()
+}
+
+// Run `instruction` with `user` followed by `wallet` and the remaining accounts,
+// first signed by another wallet and then unsigned by the rightful one.
+fn assert_requires_owner_signature(instruction: UserInstruction, extra_accounts: usize) {
+    let mut f = Fixture::new();
+    let program_id = f.program_id;
+    let mut extras: Vec<TestAccount> = (0..extra_accounts)
+        .map(|_| TestAccount::new(program_id, vec![0; 256]))
+        .collect();
+
+    let mut accounts: Vec<&mut TestAccount> = vec![&mut f.alice_user, &mut f.mallory_wallet];
+    accounts.extend(extras.iter_mut());
+    assert_eq!(
+        run(&program_id, &instruction, &mut accounts),
+        Err(unauthorized())
+    );
+
+    f.alice_wallet.is_signer = false;
+    let mut accounts: Vec<&mut TestAccount> = vec![&mut f.alice_user, &mut f.alice_wallet];
+    accounts.extend(extras.iter_mut());
+    assert_eq!(
+        run(&program_id, &instruction, &mut accounts),
+        Err(ProgramError::MissingRequiredSignature)
+    );
+}
+
+#[test]
+fn authorize_user_accepts_owner_signature() {
+    let mut f = Fixture::new();
+    let user = authorize_user(&f.program_id, &f.alice_user.info(), &f.alice_wallet.info()).unwrap();
+    assert_eq!(user.user_id, "alice");
+}
+
+#[test]
+fn authorize_user_rejects_user_account_not_derived_from_wallet() {
+    let mut f = Fixture::new();
+    // A program-owned account claiming Mallory's wallet, but not at her user PDA
+    let mut forged = TestAccount {
+        key: Pubkey::new_unique(),
+        ..user_account(&f.program_id, "alice", &f.mallory_wallet.key)
+    };
+    assert_eq!(
+        authorize_user(&f.program_id, &forged.info(), &f.mallory_wallet.info()),
+        Err(ProgramError::InvalidSeeds)
+    );
+}
+
+#[test]
+fn create_post_requires_owner_signature() {
+    assert_requires_owner_signature(
+        UserInstruction::CreatePost {
+            post_id: "post-1".to_string(),
+            user_id: "alice".to_string(),
+            content: PostContent {
+                text: "hello".to_string(),
+                ..PostContent::default()
+            },
+            timestamp: 0,
+            visibility: PostVisibility::Public,
+        },
+        3,
+    );
+}
+
+#[test]
+fn upload_media_requires_owner_signature() {
+    assert_requires_owner_signature(
+        UserInstruction::UploadMedia {
+            media_id: "media-1".to_string(),
+            post_id: "post-1".to_string(),
+            user_id: "alice".to_string(),
+            reference: MediaReference {
+                locator: MediaLocator::Url("https://example.com/a.png".to_string()),
+                sha256: [0; 32],
+                byte_size: 1024,
+                mime_type: "image/png".to_string(),
+            },
+            media_type: MediaType::Image,
+        },
+        4,
+    );
+}
+
+#[test]
+fn edit_post_requires_owner_signature() {
+    assert_requires_owner_signature(
+        UserInstruction::EditPost {
+            post_id: "post-1".to_string(),
+            content: PostContent::default(),
+        },
+        2,
+    );
+}
+
+#[test]
+fn delete_post_requires_owner_signature() {
+    assert_requires_owner_signature(
+        UserInstruction::DeletePost {
+            post_id: "post-1".to_string(),
+        },
+        2,
+    );
+}
+
+#[test]
+fn create_comment_requires_owner_signature() {
+    assert_requires_owner_signature(
+        UserInstruction::CreateComment {
+            comment_id: "comment-1".to_string(),
+            user_id: "alice".to_string(),
+            text: "nice".to_string(),
+            parent_comment: None,
+        },
+        4,
+    );
+}
+
+#[test]
+fn delete_comment_requires_owner_signature() {
+    assert_requires_owner_signature(
+        UserInstruction::DeleteComment {
+            comment_id: "comment-1".to_string(),
+        },
+        3,
+    );
+}
+
+#[test]
+fn react_requires_owner_signature() {
+    assert_requires_owner_signature(
+        UserInstruction::React {
+            user_id: "alice".to_string(),
+            target: ReactionTarget::Post,
+            kind: ReactionKind::Like,
+        },
+        4,
+    );
+}
+
+#[test]
+fn unreact_requires_owner_signature() {
+    assert_requires_owner_signature(UserInstruction::Unreact, 3);
+}
+
+#[test]
+fn follow_requires_owner_signature() {
+    assert_requires_owner_signature(UserInstruction::Follow, 3);
+}
+
+#[test]
+fn unfollow_requires_owner_signature() {
+    assert_requires_owner_signature(UserInstruction::Unfollow, 2);
+}
+
+#[test]
+fn repost_requires_owner_signature() {
+    assert_requires_owner_signature(
+        UserInstruction::Repost {
+            post_id: "post-2".to_string(),
+            user_id: "alice".to_string(),
+            quote: None,
+            timestamp: 0,
+            visibility: PostVisibility::Public,
+        },
+        5,
+    );
+}
+
+#[test]
+fn upload_manga_requires_owner_signature() {
+    assert_requires_owner_signature(
+        UserInstruction::UploadManga {
+            manga_id: "manga-1".to_string(),
+            creator_user_id: "alice".to_string(),
+            title: "Title".to_string(),
+            description: "Description".to_string(),
+            timestamp: 0,
+            is_free: false,
+        },
+        3,
+    );
+}
+
+#[test]
+fn add_chapter_requires_owner_signature() {
+    assert_requires_owner_signature(
+        UserInstruction::AddChapter {
+            manga_id: "manga-1".to_string(),
+            chapter_number: 1,
+            title: "Chapter 1".to_string(),
+            pages: vec![],
+            price: 0,
+        },
+        3,
+    );
+}
+
+#[test]
+fn remove_chapter_requires_owner_signature() {
+    assert_requires_owner_signature(
+        UserInstruction::RemoveChapter {
+            manga_id: "manga-1".to_string(),
+            chapter_number: 1,
+        },
+        2,
+    );
+}
+
+#[test]
+fn monetize_content_requires_owner_signature() {
+    assert_requires_owner_signature(
+        UserInstruction::MonetizeContent {
+            content_id: "content-1".to_string(),
+            creator_user_id: "alice".to_string(),
+            price: 10,
+            payment_mint: None,
+            access_terms: AccessTerms::Permanent,
+        },
+        2,
+    );
+}
+
+#[test]
+fn access_manga_requires_owner_signature() {
+    assert_requires_owner_signature(
+        UserInstruction::AccessManga {
+            manga_id: "manga-1".to_string(),
+            user_id: "alice".to_string(),
+            chapter: None,
+        },
+        5,
+    );
+}
+
+#[test]
+fn send_message_requires_owner_signature() {
+    assert_requires_owner_signature(
+        UserInstruction::SendMessage {
+            message_id: "message-1".to_string(),
+            sender_user_id: "alice".to_string(),
+            recipient_user_id: "bob".to_string(),
+            message_content: "hi".to_string(),
+        },
+        3,
+    );
+}
+
+#[test]
+fn send_message_rejects_existing_account() {
+    let mut f = Fixture::new();
+    let program_id = f.program_id;
+    let bob_wallet = TestAccount::wallet(false);
+    let mut bob_user = user_account(&program_id, "bob", &bob_wallet.key);
+    let mut system_program = system_program_account();
+
+    // Alice tries to write her message over Bob's user account
+    let mut target = user_account(&program_id, "bob", &bob_wallet.key);
+    let instruction = UserInstruction::SendMessage {
+        message_id: "message-1".to_string(),
+        sender_user_id: "alice".to_string(),
+        recipient_user_id: "bob".to_string(),
+        message_content: "hi".to_string(),
+    };
+    let result = run(
+        &program_id,
+        &instruction,
+        &mut [
+            &mut f.alice_user,
+            &mut f.alice_wallet,
+            &mut bob_user,
+            &mut target,
+            &mut system_program,
+        ],
+    );
+    assert_eq!(result, Err(ProgramError::AccountAlreadyInitialized));
+}
+
+fn support_instruction(recipient_user_id: &str) -> UserInstruction {
+    UserInstruction::SupportUser {
+        support_id: "support-1".to_string(),
+        sender_user_id: "alice".to_string(),
+        recipient_user_id: recipient_user_id.to_string(),
+        amount: 10,
+        timestamp: 0,
+        message: None,
+    }
+}
+
+#[test]
+fn support_user_requires_owner_signature() {
+    assert_requires_owner_signature(support_instruction("bob"), 4);
+}
+
+#[test]
+fn create_group_requires_owner_signature() {
+    assert_requires_owner_signature(
+        UserInstruction::CreateGroup {
+            group_id: "group-1".to_string(),
+        },
+        2,
+    );
+}
+
+fn group_message_instruction() -> UserInstruction {
+    UserInstruction::SendGroupMessage {
+        message_id: "message-1".to_string(),
+        group_id: "group-1".to_string(),
+        user_id: "alice".to_string(),
+        message_content: "hi".to_string(),
+    }
+}
+
+#[test]
+fn send_group_message_requires_owner_signature() {
+    assert_requires_owner_signature(group_message_instruction(), 3);
+}
+
+#[test]
+fn add_member_requires_owner_signature() {
+    assert_requires_owner_signature(
+        UserInstruction::AddMember {
+            group_id: "group-1".to_string(),
+        },
+        3,
+    );
+}
+
+#[test]
+fn set_role_requires_owner_signature() {
+    assert_requires_owner_signature(
+        UserInstruction::SetRole {
+            group_id: "group-1".to_string(),
+            role: GroupRole::Admin,
+        },
+        2,
+    );
+}
+
+#[test]
+fn group_member_cannot_be_impersonated() {
+    let mut f = Fixture::new();
+    let program_id = f.program_id;
+    let alice = f.alice_user.key;
+    let mut group = group_account(&program_id, &alice, &[("alice", alice, GroupRole::Owner)]);
+    let mut message_account = TestAccount::wallet(false);
+    let mut system_program = system_program_account();
+
+    // Mallory signs with her own wallet while naming Alice as the sender
+    let result = run(
+        &program_id,
+        &group_message_instruction(),
+        &mut [
+            &mut f.alice_user,
+            &mut f.mallory_wallet,
+            &mut group,
+            &mut message_account,
+            &mut system_program,
+        ],
+    );
+    assert_eq!(result, Err(unauthorized()));
+}
+
+fn monetized_content_account(
+    program_id: &Pubkey,
+    creator: &TestAccount,
+    creator_user_id: &str,
+) -> TestAccount {
+    let content = MonetizedContent {
+        content_id: "content-1".to_string(),
+        creator_user_id: creator_user_id.to_string(),
+        price: 10,
+        payment_mint: None,
+        revenue_shares: vec![],
+        access_terms: AccessTerms::Permanent,
+        total_sales: 0,
+        total_revenue: 0,
+    };
+    TestAccount {
+        key: find_content_address(program_id, &creator.key, "content-1").0,
+        ..TestAccount::new(*program_id, borsh::to_vec(&content).unwrap())
+    }
+}
+
+fn purchase_instruction() -> UserInstruction {
+    UserInstruction::PurchaseContent {
+        content_id: "content-1".to_string(),
+        purchaser_user_id: "alice".to_string(),
+        amount: 10,
+        timestamp: 0,
+    }
+}
+
+#[test]
+fn purchase_requires_purchaser_signature() {
+    let mut f = Fixture::new();
+    let program_id = f.program_id;
+    let mut creator_wallet = TestAccount::wallet(false);
+    let mut creator_user = user_account(&program_id, "bob", &creator_wallet.key);
+    let mut content_account = monetized_content_account(&program_id, &creator_user, "bob");
+    let mut system_program = system_program_account();
+    let mut config = TestAccount::new(program_id, vec![]);
+    let mut treasury = TestAccount::wallet(false);
+    let mut receipt = TestAccount::wallet(false);
+    let instruction = purchase_instruction();
+
+    // Mallory tries to pay from Alice's user with her own signature
+    let result = run(
+        &program_id,
+        &instruction,
+        &mut [
+            &mut creator_wallet,
+            &mut f.mallory_wallet,
+            &mut content_account,
+            &mut f.alice_user,
+            &mut creator_user,
+            &mut system_program,
+            &mut config,
+            &mut treasury,
+            &mut receipt,
+        ],
+    );
+    assert_eq!(result, Err(unauthorized()));
+
+    // Alice's wallet is passed but has not signed
+    f.alice_wallet.is_signer = false;
+    let result = run(
+        &program_id,
+        &instruction,
+        &mut [
+            &mut creator_wallet,
+            &mut f.alice_wallet,
+            &mut content_account,
+            &mut f.alice_user,
+            &mut creator_user,
+            &mut system_program,
+            &mut config,
+            &mut treasury,
+            &mut receipt,
+        ],
+    );
+    assert_eq!(result, Err(ProgramError::MissingRequiredSignature));
+}
+
+#[test]
+fn purchase_pays_only_the_creator_wallet() {
+    let mut f = Fixture::new();
+    let program_id = f.program_id;
+    let creator_wallet = TestAccount::wallet(false);
+    let mut creator_user = user_account(&program_id, "bob", &creator_wallet.key);
+    let mut content_account = monetized_content_account(&program_id, &creator_user, "bob");
+    let mut system_program = system_program_account();
+    let mut config = TestAccount::new(program_id, vec![]);
+    let mut treasury = TestAccount::wallet(false);
+    let mut receipt = TestAccount::wallet(false);
+
+    // Alice names Bob's user but routes the payment to Mallory's wallet
+    let result = run(
+        &program_id,
+        &purchase_instruction(),
+        &mut [
+            &mut f.mallory_wallet,
+            &mut f.alice_wallet,
+            &mut content_account,
+            &mut f.alice_user,
+            &mut creator_user,
+            &mut system_program,
+            &mut config,
+            &mut treasury,
+            &mut receipt,
+        ],
+    );
+    assert_eq!(result, Err(AnimeNexaError::WalletMismatch.into()));
+}
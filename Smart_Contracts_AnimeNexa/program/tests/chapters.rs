@@ -0,0 +1,273 @@
+//! Adding, updating and removing the chapters of a manga.
+//!
+//! Alice publishes her manga "manga-1".
+
+mod common;
+
+use animenexa_program::{
+    error::AnimeNexaError,
+    instruction::UserInstruction,
+    pda::{find_chapter_address, find_manga_address},
+    state::{
+        manga::{MAX_CHAPTER_PAGES, MAX_CHAPTER_TITLE_LEN},
+        Chapter, Manga, MediaLocator, MediaReference, ReactionCounts,
+    },
+};
+use common::{run, set_clock, system_program_account, Fixture, TestAccount};
+use solana_program::{hash::hashv, program_error::ProgramError};
+
+struct Series {
+    f: Fixture,
+    manga: TestAccount,
+}
+
+impl Series {
+    // Alice's manga, whose first two chapters are a free preview
+    fn new() -> Self {
+        let f = Fixture::new();
+        let manga = Manga {
+            manga_id: "manga-1".to_string(),
+            creator_user_id: "alice".to_string(),
+            title: "Manga".to_string(),
+            description: String::new(),
+            timestamp: 0,
+            is_free: false,
+            chapter_count: 0,
+            free_chapters: 2,
+            reactions: ReactionCounts::default(),
+        };
+        let address = find_manga_address(&f.program_id, &f.alice_user.key, "manga-1").0;
+        Self {
+            manga: TestAccount::state(&f.program_id, address, &manga),
+            f,
+        }
+    }
+
+    // The address of chapter `chapter_number`, before anything was created there
+    fn chapter_address(&self, chapter_number: u32) -> TestAccount {
+        let address = find_chapter_address(&self.f.program_id, &self.manga.key, chapter_number).0;
+        TestAccount::empty(address)
+    }
+
+    // Run AddChapter or UpdateChapter for Alice
+    fn run_chapter(
+        &mut self,
+        instruction: UserInstruction,
+        chapter: &mut TestAccount,
+    ) -> Result<(), ProgramError> {
+        let program_id = self.f.program_id;
+        let mut system_program = system_program_account();
+        run(
+            &program_id,
+            &instruction,
+            &mut [
+                &mut self.f.alice_user,
+                &mut self.f.alice_wallet,
+                &mut self.manga,
+                chapter,
+                &mut system_program,
+            ],
+        )
+    }
+}
+
+// Page `n` of a chapter, a PNG whose SHA-256 is `n` repeated
+fn page(n: u8) -> MediaReference {
+    MediaReference {
+        locator: MediaLocator::Url(format!("https://example.com/{}.png", n)),
+        sha256: [n; 32],
+        byte_size: 1024,
+        mime_type: "image/png".to_string(),
+    }
+}
+
+fn add_chapter(chapter_number: u32) -> UserInstruction {
+    UserInstruction::AddChapter {
+        manga_id: "manga-1".to_string(),
+        chapter_number,
+        title: format!("Chapter {}", chapter_number),
+        pages: vec![page(1), page(2)],
+        price: 100,
+    }
+}
+
+fn update_chapter(chapter_number: u32) -> UserInstruction {
+    UserInstruction::UpdateChapter {
+        manga_id: "manga-1".to_string(),
+        chapter_number,
+        title: format!("Chapter {}", chapter_number),
+        pages: vec![page(1), page(2)],
+        price: 100,
+    }
+}
+
+#[test]
+fn add_chapter_counts_it_on_the_manga() {
+    let mut s = Series::new();
+    let mut chapter = s.chapter_address(1);
+
+    s.run_chapter(add_chapter(1), &mut chapter).unwrap();
+
+    assert_eq!(chapter.read::<Chapter>().chapter_number, 1);
+    assert_eq!(s.manga.read::<Manga>().chapter_count, 1);
+}
+
+#[test]
+fn add_chapter_hashes_its_pages_in_order() {
+    let mut s = Series::new();
+    let mut chapter = s.chapter_address(1);
+
+    s.run_chapter(add_chapter(1), &mut chapter).unwrap();
+
+    let content_hash = hashv(&[&[1; 32], &[2; 32]]).to_bytes();
+    assert_eq!(chapter.read::<Chapter>().content_hash, content_hash);
+}
+
+#[test]
+fn update_chapter_replaces_its_contents() {
+    let mut s = Series::new();
+    let mut chapter = s.chapter_address(1);
+    set_clock(10);
+    s.run_chapter(add_chapter(1), &mut chapter).unwrap();
+
+    set_clock(20);
+    let update = UserInstruction::UpdateChapter {
+        manga_id: "manga-1".to_string(),
+        chapter_number: 1,
+        title: "Chapter 1, redrawn".to_string(),
+        pages: vec![page(2), page(1), page(3)],
+        price: 250,
+    };
+    s.run_chapter(update, &mut chapter).unwrap();
+
+    let updated: Chapter = chapter.read();
+    assert_eq!(updated.title, "Chapter 1, redrawn");
+    assert_eq!(updated.pages, [page(2), page(1), page(3)]);
+    assert_eq!(
+        updated.content_hash,
+        hashv(&[&[2; 32], &[1; 32], &[3; 32]]).to_bytes()
+    );
+    assert_eq!(updated.price, 250);
+    assert_eq!(updated.published_at, 10);
+    assert_eq!(updated.updated_at, 20);
+    assert_eq!(s.manga.read::<Manga>().chapter_count, 1);
+}
+
+#[test]
+fn remove_chapter_closes_it_and_refunds_the_wallet() {
+    let mut s = Series::new();
+    let mut chapter = s.chapter_address(1);
+    s.run_chapter(add_chapter(1), &mut chapter).unwrap();
+    let wallet_lamports = s.f.alice_wallet.lamports;
+    let rent = chapter.lamports;
+
+    let remove = UserInstruction::RemoveChapter {
+        manga_id: "manga-1".to_string(),
+        chapter_number: 1,
+    };
+    s.run_chapter(remove, &mut chapter).unwrap();
+
+    assert_eq!(chapter.lamports, 0);
+    assert!(chapter.data.is_empty());
+    assert_eq!(chapter.owner, solana_program::system_program::id());
+    assert_eq!(s.f.alice_wallet.lamports, wallet_lamports + rent);
+    assert_eq!(s.manga.read::<Manga>().chapter_count, 0);
+}
+
+#[test]
+fn chapters_reject_too_many_pages() {
+    let mut s = Series::new();
+    let mut chapter = s.chapter_address(1);
+    let pages: Vec<_> = (0..=MAX_CHAPTER_PAGES).map(|n| page(n as u8)).collect();
+
+    let add = UserInstruction::AddChapter {
+        manga_id: "manga-1".to_string(),
+        chapter_number: 1,
+        title: "Chapter 1".to_string(),
+        pages: pages.clone(),
+        price: 100,
+    };
+    assert_eq!(
+        s.run_chapter(add, &mut chapter),
+        Err(AnimeNexaError::TooManyPages.into())
+    );
+
+    s.run_chapter(add_chapter(1), &mut chapter).unwrap();
+    let update = UserInstruction::UpdateChapter {
+        manga_id: "manga-1".to_string(),
+        chapter_number: 1,
+        title: "Chapter 1".to_string(),
+        pages,
+        price: 100,
+    };
+    assert_eq!(
+        s.run_chapter(update, &mut chapter),
+        Err(AnimeNexaError::TooManyPages.into())
+    );
+}
+
+#[test]
+fn chapters_reject_a_title_too_long() {
+    let mut s = Series::new();
+    let mut chapter = s.chapter_address(1);
+    let title = "a".repeat(MAX_CHAPTER_TITLE_LEN + 1);
+
+    let add = UserInstruction::AddChapter {
+        manga_id: "manga-1".to_string(),
+        chapter_number: 1,
+        title: title.clone(),
+        pages: vec![],
+        price: 100,
+    };
+    assert_eq!(
+        s.run_chapter(add, &mut chapter),
+        Err(AnimeNexaError::TextTooLong.into())
+    );
+
+    s.run_chapter(add_chapter(1), &mut chapter).unwrap();
+    let update = UserInstruction::UpdateChapter {
+        manga_id: "manga-1".to_string(),
+        chapter_number: 1,
+        title,
+        pages: vec![],
+        price: 100,
+    };
+    assert_eq!(
+        s.run_chapter(update, &mut chapter),
+        Err(AnimeNexaError::TextTooLong.into())
+    );
+}
+
+#[test]
+fn add_chapter_rejects_chapter_zero() {
+    let mut s = Series::new();
+    let mut chapter = s.chapter_address(0);
+
+    assert_eq!(
+        s.run_chapter(add_chapter(0), &mut chapter),
+        Err(AnimeNexaError::InvalidChapterNumber.into())
+    );
+}
+
+#[test]
+fn update_chapter_rejects_chapter_zero() {
+    let mut s = Series::new();
+    // A chapter 0 stored before it was rejected
+    let chapter = Chapter {
+        manga_id: "manga-1".to_string(),
+        chapter_number: 0,
+        title: "Prologue".to_string(),
+        pages: vec![],
+        content_hash: Chapter::hash_pages(&[]),
+        price: 100,
+        published_at: 0,
+        updated_at: 0,
+    };
+    let address = s.chapter_address(0).key;
+    let mut chapter = TestAccount::state(&s.f.program_id, address, &chapter);
+
+    assert_eq!(
+        s.run_chapter(update_chapter(0), &mut chapter),
+        Err(AnimeNexaError::InvalidChapterNumber.into())
+    );
+}
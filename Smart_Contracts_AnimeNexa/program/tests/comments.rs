@@ -0,0 +1,208 @@
+//! Comment threads: counting comments and replies, and deleting comments.
+//!
+//! Alice comments on Bob's post "post-1".
+
+mod common;
+
+use animenexa_program::{
+    error::AnimeNexaError,
+    instruction::UserInstruction,
+    pda::find_comment_address,
+    state::{Comment, Post},
+};
+use common::{
+    comment, comment_account, post, post_account, run, set_clock, system_program_account,
+    user_account, Fixture, TestAccount,
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey, rent::Rent};
+
+struct Thread {
+    f: Fixture,
+    bob_user: TestAccount,
+    post: TestAccount,
+}
+
+impl Thread {
+    fn new() -> Self {
+        let f = Fixture::new();
+        let bob_user = user_account(&f.program_id, "bob", &Pubkey::new_unique());
+        let post = post_account(&f.program_id, &bob_user, &post("post-1", "bob", "hello"));
+        Self { f, bob_user, post }
+    }
+
+    // A comment by Bob in the thread of `post`
+    fn bob_comment(&self, comment_id: &str, post: Pubkey) -> TestAccount {
+        let comment = comment(comment_id, &self.bob_user, post, None);
+        comment_account(&self.f.program_id, &comment)
+    }
+
+    // Run CreateComment for Alice, returning her new comment account
+    fn create(&mut self, parent: Option<&mut TestAccount>) -> Result<TestAccount, ProgramError> {
+        let program_id = self.f.program_id;
+        let address = find_comment_address(&program_id, &self.f.alice_user.key, "comment-1").0;
+        let mut comment = TestAccount::empty(address);
+        let mut system_program = system_program_account();
+
+        let instruction = UserInstruction::CreateComment {
+            comment_id: "comment-1".to_string(),
+            user_id: "alice".to_string(),
+            text: "nice".to_string(),
+            parent_comment: parent.as_ref().map(|parent| parent.key),
+        };
+        let mut accounts = vec![
+            &mut self.f.alice_user,
+            &mut self.f.alice_wallet,
+            &mut self.post,
+            &mut self.bob_user,
+            &mut comment,
+            &mut system_program,
+        ];
+        accounts.extend(parent);
+        run(&program_id, &instruction, &mut accounts)?;
+
+        Ok(comment)
+    }
+
+    // Run DeleteComment for Alice, passing the parent of a reply
+    fn delete(
+        &mut self,
+        comment: &mut TestAccount,
+        parent: Option<&mut TestAccount>,
+    ) -> Result<(), ProgramError> {
+        let program_id = self.f.program_id;
+        let mut system_program = system_program_account();
+        let instruction = UserInstruction::DeleteComment {
+            comment_id: "comment-1".to_string(),
+        };
+        let mut accounts = vec![
+            &mut self.f.alice_user,
+            &mut self.f.alice_wallet,
+            comment,
+            &mut self.post,
+            &mut system_program,
+        ];
+        accounts.extend(parent);
+        run(&program_id, &instruction, &mut accounts)
+    }
+}
+
+#[test]
+fn create_comment_counts_it_on_the_post() {
+    let mut t = Thread::new();
+    set_clock(42);
+
+    let comment: Comment = t.create(None).unwrap().read();
+    assert_eq!(comment.author, t.f.alice_user.key);
+    assert_eq!(comment.post, t.post.key);
+    assert_eq!(comment.parent_comment, None);
+    assert_eq!(comment.created_at, 42);
+    assert_eq!(t.post.read::<Post>().comment_count, 1);
+}
+
+#[test]
+fn reply_counts_on_the_parent_and_the_post() {
+    let mut t = Thread::new();
+    let mut parent = t.bob_comment("comment-0", t.post.key);
+
+    let reply: Comment = t.create(Some(&mut parent)).unwrap().read();
+    assert_eq!(reply.parent_comment, Some(parent.key));
+    assert_eq!(parent.read::<Comment>().reply_count, 1);
+    assert_eq!(t.post.read::<Post>().comment_count, 1);
+}
+
+#[test]
+fn reply_to_a_comment_on_another_post_is_rejected() {
+    let mut t = Thread::new();
+    let mut parent = t.bob_comment("comment-0", Pubkey::new_unique());
+
+    assert_eq!(
+        t.create(Some(&mut parent)).err(),
+        Some(AnimeNexaError::CommentNotInThread.into())
+    );
+}
+
+#[test]
+fn reply_to_a_deleted_comment_is_rejected() {
+    let mut t = Thread::new();
+    let mut parent = t.bob_comment("comment-0", t.post.key);
+    let mut deleted: Comment = parent.read();
+    deleted.deleted = true;
+    parent.data = borsh::to_vec(&deleted).unwrap();
+
+    assert_eq!(
+        t.create(Some(&mut parent)).err(),
+        Some(AnimeNexaError::CommentDeleted.into())
+    );
+}
+
+#[test]
+fn comment_on_a_deleted_post_is_rejected() {
+    let mut t = Thread::new();
+    let mut deleted: Post = t.post.read();
+    deleted.deleted = true;
+    t.post.data = borsh::to_vec(&deleted).unwrap();
+
+    assert_eq!(
+        t.create(None).err(),
+        Some(AnimeNexaError::PostDeleted.into())
+    );
+}
+
+#[test]
+fn delete_comment_tombstones_it_and_uncounts_it() {
+    let mut t = Thread::new();
+    let mut comment = t.create(None).unwrap();
+    let wallet_lamports = t.f.alice_wallet.lamports;
+    let comment_lamports = comment.lamports;
+
+    t.delete(&mut comment, None).unwrap();
+
+    let deleted: Comment = comment.read();
+    assert!(deleted.deleted);
+    assert!(deleted.text.is_empty());
+    assert_eq!(t.post.read::<Post>().comment_count, 0);
+
+    // The rent the shrunk comment no longer needs goes back to the wallet
+    assert_eq!(
+        comment.lamports,
+        Rent::default().minimum_balance(comment.data.len())
+    );
+    assert_eq!(
+        t.f.alice_wallet.lamports,
+        wallet_lamports + comment_lamports - comment.lamports
+    );
+
+    assert_eq!(
+        t.delete(&mut comment, None),
+        Err(AnimeNexaError::CommentDeleted.into())
+    );
+
+    // Deleting a reply also uncounts it on its parent
+    let mut parent = t.bob_comment("comment-0", t.post.key);
+    let mut reply = t.create(Some(&mut parent)).unwrap();
+    assert_eq!(parent.read::<Comment>().reply_count, 1);
+    assert_eq!(t.post.read::<Post>().comment_count, 1);
+
+    assert_eq!(
+        t.delete(
+            &mut reply,
+            Some(&mut TestAccount::empty(Pubkey::new_unique()))
+        ),
+        Err(ProgramError::InvalidArgument)
+    );
+    t.delete(&mut reply, Some(&mut parent)).unwrap();
+    assert!(reply.read::<Comment>().deleted);
+    assert_eq!(parent.read::<Comment>().reply_count, 0);
+    assert_eq!(t.post.read::<Post>().comment_count, 0);
+}
+
+#[test]
+fn delete_comment_rejects_another_users_comment() {
+    let mut t = Thread::new();
+    let mut comment = t.bob_comment("comment-1", t.post.key);
+
+    assert_eq!(
+        t.delete(&mut comment, None),
+        Err(AnimeNexaError::Unauthorized.into())
+    );
+}
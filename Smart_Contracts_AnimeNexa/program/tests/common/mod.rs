@@ -0,0 +1,525 @@
+//! Accounts and a minimal runtime for driving the processor in tests.
+//!
+//! `run` lays each account out the way the loader serializes it, so the
+//! program can resize accounts, and stands in for the runtime's syscalls: the
+//! clock and rent sysvars, logged data, and CPIs into the system program or
+//! SPL token transfers.
+//! Every integration test runs the processor this way, without a validator.
+
+#![allow(dead_code)]
+
+use std::{cell::Cell, cell::RefCell, sync::Once};
+
+use animenexa_program::{
+    instruction::UserInstruction,
+    pda::{find_comment_address, find_group_address, find_post_address, find_user_address},
+    processor::process_instruction,
+    state::{
+        Comment, Group, GroupMember, GroupRole, Post, PostContent, PostVisibility, ReactionCounts,
+        User,
+    },
+};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE, SUCCESS},
+    instruction::Instruction,
+    program_error::ProgramError,
+    program_option::COption,
+    program_pack::Pack,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::SystemInstruction,
+    system_program,
+};
+use spl_token::{
+    error::TokenError,
+    instruction::TokenInstruction,
+    state::{Account as TokenAccountState, AccountState, Mint},
+};
+
+pub struct TestAccount {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub is_signer: bool,
+}
+
+impl TestAccount {
+    pub fn new(owner: Pubkey, data: Vec<u8>) -> Self {
+        Self {
+            key: Pubkey::new_unique(),
+            owner,
+            lamports: 1_000_000_000,
+            data,
+            is_signer: false,
+        }
+    }
+
+    pub fn wallet(is_signer: bool) -> Self {
+        Self {
+            is_signer,
+            ..Self::new(system_program::id(), vec![])
+        }
+    }
+
+    // A program-owned account at `key` holding `state`, funded rent-exempt
+    pub fn state<T: borsh::BorshSerialize>(program_id: &Pubkey, key: Pubkey, state: &T) -> Self {
+        let data = borsh::to_vec(state).unwrap();
+        Self {
+            key,
+            lamports: Rent::default().minimum_balance(data.len()),
+            ..Self::new(*program_id, data)
+        }
+    }
+
+    // An address nothing has been created at yet
+    pub fn empty(key: Pubkey) -> Self {
+        Self {
+            key,
+            lamports: 0,
+            ..Self::wallet(false)
+        }
+    }
+
+    pub fn read<T: borsh::BorshDeserialize>(&self) -> T {
+        borsh::from_slice(&self.data).unwrap()
+    }
+
+    // A plain view of the account; the program must not resize it
+    pub fn info(&mut self) -> AccountInfo<'_> {
+        AccountInfo::new(
+            &self.key,
+            self.is_signer,
+            true,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            false,
+            0,
+        )
+    }
+}
+
+pub struct Fixture {
+    pub program_id: Pubkey,
+    pub alice_wallet: TestAccount,
+    pub alice_user: TestAccount,
+    pub mallory_wallet: TestAccount,
+}
+
+impl Fixture {
+    pub fn new() -> Self {
+        let program_id = Pubkey::new_unique();
+        let alice_wallet = TestAccount::wallet(true);
+        let alice_user = user_account(&program_id, "alice", &alice_wallet.key);
+        Self {
+            program_id,
+            alice_wallet,
+            alice_user,
+            mallory_wallet: TestAccount::wallet(true),
+        }
+    }
+}
+
+pub fn user_account(program_id: &Pubkey, user_id: &str, wallet: &Pubkey) -> TestAccount {
+    let user = User {
+        user_id: user_id.to_string(),
+        username: user_id.to_string(),
+        public_key: *wallet,
+        profile_data: String::new(),
+        tips_received_total: 0,
+        tips_received_count: 0,
+        follower_count: 0,
+        following_count: 0,
+    };
+    TestAccount {
+        key: find_user_address(program_id, wallet).0,
+        ..TestAccount::new(*program_id, borsh::to_vec(&user).unwrap())
+    }
+}
+
+// A live post by `author`, with no media, comments, reactions or reposts
+pub fn post(post_id: &str, author_id: &str, text: &str) -> Post {
+    Post {
+        post_id: post_id.to_string(),
+        user_id: author_id.to_string(),
+        content: PostContent {
+            text: text.to_string(),
+            ..PostContent::default()
+        },
+        timestamp: 0,
+        media_ids: vec![],
+        revision: 0,
+        previous_content_hash: None,
+        deleted: false,
+        comment_count: 0,
+        reactions: ReactionCounts::default(),
+        visibility: PostVisibility::Public,
+        repost_of: None,
+        repost_count: 0,
+    }
+}
+
+// `post` stored at its PDA under `author`
+pub fn post_account(program_id: &Pubkey, author: &TestAccount, post: &Post) -> TestAccount {
+    let address = find_post_address(program_id, &author.key, &post.post_id).0;
+    TestAccount::state(program_id, address, post)
+}
+
+// A live comment by the user in `author` in the thread of `post`, with no
+// replies or reactions
+pub fn comment(
+    comment_id: &str,
+    author: &TestAccount,
+    post: Pubkey,
+    parent_comment: Option<Pubkey>,
+) -> Comment {
+    Comment {
+        comment_id: comment_id.to_string(),
+        user_id: author.read::<User>().user_id,
+        author: author.key,
+        post,
+        parent_comment,
+        text: "first!".to_string(),
+        created_at: 0,
+        reply_count: 0,
+        deleted: false,
+        reactions: ReactionCounts::default(),
+    }
+}
+
+// `comment` stored at its PDA under its author
+pub fn comment_account(program_id: &Pubkey, comment: &Comment) -> TestAccount {
+    let address = find_comment_address(program_id, &comment.author, &comment.comment_id).0;
+    TestAccount::state(program_id, address, comment)
+}
+
+// A group "group-1" created by `creator`, with `members` in the given roles
+pub fn group_account(
+    program_id: &Pubkey,
+    creator: &Pubkey,
+    members: &[(&str, Pubkey, GroupRole)],
+) -> TestAccount {
+    let group = Group {
+        group_id: "group-1".to_string(),
+        creator: *creator,
+        owner: members
+            .iter()
+            .find(|(_, _, role)| *role == GroupRole::Owner)
+            .map_or(*creator, |(_, user, _)| *user),
+        members: members
+            .iter()
+            .map(|(user_id, user, role)| GroupMember {
+                user_id: user_id.to_string(),
+                user: *user,
+                role: *role,
+            })
+            .collect(),
+    };
+    TestAccount {
+        key: find_group_address(program_id, creator, "group-1").0,
+        ..TestAccount::new(*program_id, borsh::to_vec(&group).unwrap())
+    }
+}
+
+// An initialized SPL token mint at `key` with `decimals`
+pub fn mint_account(key: Pubkey, decimals: u8) -> TestAccount {
+    let mint = Mint {
+        mint_authority: COption::None,
+        supply: u64::MAX,
+        decimals,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    };
+    let mut data = vec![0; Mint::LEN];
+    Mint::pack(mint, &mut data).unwrap();
+    TestAccount {
+        key,
+        ..TestAccount::new(spl_token::id(), data)
+    }
+}
+
+// A token account at `key` holding `amount` of `mint` for `owner`
+pub fn token_account(key: Pubkey, mint: &Pubkey, owner: &Pubkey, amount: u64) -> TestAccount {
+    let account = TokenAccountState {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    };
+    let mut data = vec![0; TokenAccountState::LEN];
+    TokenAccountState::pack(account, &mut data).unwrap();
+    TestAccount {
+        key,
+        ..TestAccount::new(spl_token::id(), data)
+    }
+}
+
+pub fn token_program_account() -> TestAccount {
+    TestAccount {
+        key: spl_token::id(),
+        ..TestAccount::new(Pubkey::default(), vec![])
+    }
+}
+
+pub fn system_program_account() -> TestAccount {
+    TestAccount {
+        key: system_program::id(),
+        ..TestAccount::new(Pubkey::default(), vec![])
+    }
+}
+
+// Run `instruction` against `accounts`. As on chain, the accounts are only
+// updated when the instruction succeeds.
+pub fn run(
+    program_id: &Pubkey,
+    instruction: &UserInstruction,
+    accounts: &mut [&mut TestAccount],
+) -> Result<(), ProgramError> {
+    install_runtime();
+
+    let mut slots: Vec<Slot> = accounts.iter().map(|account| Slot::new(account)).collect();
+    let result = {
+        let infos: Vec<AccountInfo> = slots.iter_mut().map(Slot::info).collect();
+        process_instruction(program_id, &infos, &borsh::to_vec(instruction).unwrap())
+    };
+
+    if result.is_ok() {
+        for (account, slot) in accounts.iter_mut().zip(&slots) {
+            slot.write_back(account);
+        }
+    }
+    result
+}
+
+// Set the Unix timestamp returned by the clock sysvar on this thread
+pub fn set_clock(unix_timestamp: i64) {
+    NOW.with(|now| now.set(unix_timestamp));
+}
+
+// Take the data logged with `sol_log_data` on this thread so far
+pub fn take_logged_data() -> Vec<Vec<u8>> {
+    LOGGED_DATA.with(|logged| logged.take())
+}
+
+thread_local! {
+    static NOW: Cell<i64> = const { Cell::new(0) };
+    static LOGGED_DATA: RefCell<Vec<Vec<u8>>> = const { RefCell::new(Vec::new()) };
+}
+
+// The key preceded by the account's original data length, as serialized by
+// the loader; `AccountInfo::realloc` reads the length from there
+#[repr(C)]
+struct KeySlot {
+    original_data_len: u32,
+    key: Pubkey,
+}
+
+// An account serialized the way the loader does it: the data is preceded by
+// its length and followed by room to grow
+struct Slot {
+    key: KeySlot,
+    owner: Pubkey,
+    lamports: u64,
+    buffer: Vec<u64>,
+    is_signer: bool,
+}
+
+impl Slot {
+    fn new(account: &TestAccount) -> Self {
+        let len = account.data.len();
+        let mut buffer = vec![0u64; 1 + (len + MAX_PERMITTED_DATA_INCREASE).div_ceil(8)];
+        buffer[0] = len as u64;
+        let mut slot = Self {
+            key: KeySlot {
+                original_data_len: len as u32,
+                key: account.key,
+            },
+            owner: account.owner,
+            lamports: account.lamports,
+            buffer,
+            is_signer: account.is_signer,
+        };
+        slot.data_mut(len).copy_from_slice(&account.data);
+        slot
+    }
+
+    fn data_mut(&mut self, len: usize) -> &mut [u8] {
+        // The data starts right after the length, within the buffer
+        unsafe { std::slice::from_raw_parts_mut(self.buffer.as_mut_ptr().add(1) as *mut u8, len) }
+    }
+
+    fn info(&mut self) -> AccountInfo<'_> {
+        let len = self.buffer[0] as usize;
+        let data = unsafe {
+            std::slice::from_raw_parts_mut(self.buffer.as_mut_ptr().add(1) as *mut u8, len)
+        };
+        AccountInfo::new(
+            &self.key.key,
+            self.is_signer,
+            true,
+            &mut self.lamports,
+            data,
+            &self.owner,
+            false,
+            0,
+        )
+    }
+
+    fn write_back(&self, account: &mut TestAccount) {
+        let len = self.buffer[0] as usize;
+        let data =
+            unsafe { std::slice::from_raw_parts(self.buffer.as_ptr().add(1) as *const u8, len) };
+        account.owner = self.owner;
+        account.lamports = self.lamports;
+        account.data = data.to_vec();
+    }
+}
+
+fn install_runtime() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        set_syscall_stubs(Box::new(TestRuntime));
+    });
+}
+
+struct TestRuntime;
+
+impl SyscallStubs for TestRuntime {
+    fn sol_log(&self, _message: &str) {}
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        LOGGED_DATA.with(|logged| {
+            logged
+                .borrow_mut()
+                .extend(fields.iter().map(|field| field.to_vec()))
+        });
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock {
+            unix_timestamp: NOW.with(Cell::get),
+            ..Clock::default()
+        };
+        unsafe { *(var_addr as *mut Clock) = clock };
+        SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+
+    // Only the system program and SPL token instructions the program uses are
+    // supported
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let account = |index: usize| {
+            let key = instruction.accounts[index].pubkey;
+            account_infos
+                .iter()
+                .find(|info| *info.key == key)
+                .expect("CPI account was not passed")
+        };
+
+        if instruction.program_id == spl_token::id() {
+            return match TokenInstruction::unpack(&instruction.data)? {
+                TokenInstruction::TransferChecked { amount, decimals } => transfer_checked(
+                    account(0),
+                    account(1),
+                    account(2),
+                    account(3),
+                    amount,
+                    decimals,
+                ),
+                other => panic!("unsupported token instruction: {:?}", other),
+            };
+        }
+        assert_eq!(
+            instruction.program_id,
+            system_program::id(),
+            "unsupported CPI"
+        );
+
+        match bincode::deserialize(&instruction.data).unwrap() {
+            SystemInstruction::CreateAccount {
+                lamports,
+                space,
+                owner,
+            } => {
+                move_lamports(account(0), account(1), lamports)?;
+                account(1).realloc(space as usize, true)?;
+                account(1).assign(&owner);
+            }
+            SystemInstruction::Transfer { lamports } => {
+                move_lamports(account(0), account(1), lamports)?
+            }
+            SystemInstruction::Allocate { space } => account(0).realloc(space as usize, true)?,
+            SystemInstruction::Assign { owner } => account(0).assign(&owner),
+            other => panic!("unsupported system instruction: {:?}", other),
+        }
+        Ok(())
+    }
+}
+
+fn move_lamports(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    let remaining = from
+        .lamports()
+        .checked_sub(lamports)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    **from.try_borrow_mut_lamports()? = remaining;
+    **to.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
+
+// Move `amount` of `mint` between token accounts, with the checks the token
+// program makes for an owner-signed TransferChecked
+fn transfer_checked<'a>(
+    source: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    for account in [source, mint, destination] {
+        if *account.owner != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+    if Mint::unpack(&mint.data.borrow())?.decimals != decimals {
+        return Err(TokenError::MintDecimalsMismatch.into());
+    }
+
+    let mut from = TokenAccountState::unpack(&source.data.borrow())?;
+    let mut to = TokenAccountState::unpack(&destination.data.borrow())?;
+    if from.mint != *mint.key || to.mint != *mint.key {
+        return Err(TokenError::MintMismatch.into());
+    }
+    if from.owner != *authority.key {
+        return Err(TokenError::OwnerMismatch.into());
+    }
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    from.amount = from
+        .amount
+        .checked_sub(amount)
+        .ok_or(TokenError::InsufficientFunds)?;
+    to.amount = to.amount.checked_add(amount).ok_or(TokenError::Overflow)?;
+    TokenAccountState::pack(from, &mut source.data.borrow_mut())?;
+    TokenAccountState::pack(to, &mut destination.data.borrow_mut())?;
+    Ok(())
+}
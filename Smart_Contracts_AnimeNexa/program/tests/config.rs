@@ -0,0 +1,132 @@
+//! Creating and updating the platform config.
+
+mod common;
+
+use animenexa_program::{
+    error::AnimeNexaError, instruction::UserInstruction, pda::find_config_address,
+    state::PlatformConfig,
+};
+use common::{run, system_program_account, TestAccount};
+use solana_program::{
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+// The program data account of `program_id`, upgradeable by `upgrade_authority`
+fn program_data_account(program_id: &Pubkey, upgrade_authority: &Pubkey) -> TestAccount {
+    let state = UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: Some(*upgrade_authority),
+    };
+    TestAccount {
+        key: Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id()).0,
+        ..TestAccount::new(
+            bpf_loader_upgradeable::id(),
+            bincode::serialize(&state).unwrap(),
+        )
+    }
+}
+
+// Run InitializeConfig signed by `admin`, returning the new config account
+fn initialize(
+    program_id: &Pubkey,
+    admin: &mut TestAccount,
+    program_data: &mut TestAccount,
+    fee_basis_points: u16,
+) -> Result<TestAccount, ProgramError> {
+    let mut config = TestAccount::empty(find_config_address(program_id).0);
+    let mut system_program = system_program_account();
+    let instruction = UserInstruction::InitializeConfig {
+        fee_basis_points,
+        treasury: Pubkey::new_unique(),
+    };
+    run(
+        program_id,
+        &instruction,
+        &mut [admin, &mut config, program_data, &mut system_program],
+    )?;
+    Ok(config)
+}
+
+// Run UpdateConfig signed by `admin`
+fn update(
+    program_id: &Pubkey,
+    admin: &mut TestAccount,
+    config: &mut TestAccount,
+    fee_basis_points: u16,
+    treasury: Pubkey,
+) -> Result<(), ProgramError> {
+    let instruction = UserInstruction::UpdateConfig {
+        fee_basis_points,
+        treasury,
+    };
+    run(program_id, &instruction, &mut [admin, config])
+}
+
+#[test]
+fn upgrade_authority_initializes_the_config() {
+    let program_id = Pubkey::new_unique();
+    let mut admin = TestAccount::wallet(true);
+    let mut program_data = program_data_account(&program_id, &admin.key);
+
+    let config: PlatformConfig = initialize(&program_id, &mut admin, &mut program_data, 250)
+        .unwrap()
+        .read();
+    assert_eq!(config.admin, admin.key);
+    assert_eq!(config.fee_basis_points, 250);
+}
+
+#[test]
+fn initialize_config_rejects_another_wallet() {
+    let program_id = Pubkey::new_unique();
+    let mut mallory = TestAccount::wallet(true);
+    let mut program_data = program_data_account(&program_id, &Pubkey::new_unique());
+
+    assert_eq!(
+        initialize(&program_id, &mut mallory, &mut program_data, 250).err(),
+        Some(AnimeNexaError::Unauthorized.into())
+    );
+}
+
+#[test]
+fn initialize_config_rejects_fee_over_the_whole() {
+    let program_id = Pubkey::new_unique();
+    let mut admin = TestAccount::wallet(true);
+    let mut program_data = program_data_account(&program_id, &admin.key);
+
+    assert_eq!(
+        initialize(&program_id, &mut admin, &mut program_data, 10_001).err(),
+        Some(AnimeNexaError::InvalidBasisPoints.into())
+    );
+}
+
+#[test]
+fn admin_updates_the_config() {
+    let program_id = Pubkey::new_unique();
+    let mut admin = TestAccount::wallet(true);
+    let mut program_data = program_data_account(&program_id, &admin.key);
+    let mut config = initialize(&program_id, &mut admin, &mut program_data, 250).unwrap();
+    let treasury = Pubkey::new_unique();
+
+    update(&program_id, &mut admin, &mut config, 500, treasury).unwrap();
+
+    let config: PlatformConfig = config.read();
+    assert_eq!(config.fee_basis_points, 500);
+    assert_eq!(config.treasury, treasury);
+}
+
+#[test]
+fn update_config_rejects_another_wallet() {
+    let program_id = Pubkey::new_unique();
+    let mut admin = TestAccount::wallet(true);
+    let mut program_data = program_data_account(&program_id, &admin.key);
+    let mut config = initialize(&program_id, &mut admin, &mut program_data, 250).unwrap();
+    let mut mallory = TestAccount::wallet(true);
+    let mallory_treasury = mallory.key;
+
+    assert_eq!(
+        update(&program_id, &mut mallory, &mut config, 0, mallory_treasury),
+        Err(AnimeNexaError::Unauthorized.into())
+    );
+}
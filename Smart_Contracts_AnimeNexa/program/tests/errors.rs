@@ -0,0 +1,46 @@
+//! Decoding the custom errors returned by the program on the client side.
+
+use animenexa_program::error::AnimeNexaError;
+use solana_program::program_error::ProgramError;
+
+#[test]
+fn errors_round_trip_through_their_custom_code() {
+    for error in [
+        AnimeNexaError::UsernameTaken,
+        AnimeNexaError::Unauthorized,
+        AnimeNexaError::AlreadyPurchased,
+        AnimeNexaError::CannotTipSelf,
+    ] {
+        let program_error = ProgramError::from(error);
+        assert_eq!(program_error, ProgramError::Custom(error as u32));
+        assert_eq!(
+            AnimeNexaError::from_program_error(&program_error),
+            Some(error)
+        );
+    }
+}
+
+#[test]
+fn codes_start_at_zero_in_declaration_order() {
+    assert_eq!(
+        AnimeNexaError::from_code(0),
+        Some(AnimeNexaError::UsernameTaken)
+    );
+    assert_eq!(
+        AnimeNexaError::from_code(AnimeNexaError::CannotTipSelf as u32),
+        Some(AnimeNexaError::CannotTipSelf)
+    );
+}
+
+#[test]
+fn other_errors_do_not_decode() {
+    assert_eq!(
+        AnimeNexaError::from_program_error(&ProgramError::MissingRequiredSignature),
+        None
+    );
+    assert_eq!(
+        AnimeNexaError::from_program_error(&ProgramError::Custom(u32::MAX)),
+        None
+    );
+    assert_eq!(AnimeNexaError::from_code(1_000), None);
+}
@@ -0,0 +1,132 @@
+//! Following and unfollowing users, and the counters kept on both of them.
+//!
+//! Alice follows Bob.
+
+mod common;
+
+use animenexa_program::{
+    error::AnimeNexaError,
+    instruction::UserInstruction,
+    pda::find_follow_address,
+    state::{Follow, User},
+};
+use common::{run, set_clock, system_program_account, user_account, Fixture, TestAccount};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+// Run Follow or Unfollow for Alice towards `followee`
+fn run_follow(
+    f: &mut Fixture,
+    instruction: UserInstruction,
+    followee: &mut TestAccount,
+    follow: &mut TestAccount,
+) -> Result<(), ProgramError> {
+    let program_id = f.program_id;
+    let mut system_program = system_program_account();
+    let mut accounts = vec![&mut f.alice_user, &mut f.alice_wallet, followee, follow];
+    if instruction == UserInstruction::Follow {
+        accounts.push(&mut system_program);
+    }
+    run(&program_id, &instruction, &mut accounts)
+}
+
+// Bob's user account and the address of Alice's follow edge towards him
+fn bob(f: &Fixture) -> (TestAccount, TestAccount) {
+    let bob_user = user_account(&f.program_id, "bob", &Pubkey::new_unique());
+    let follow_address = find_follow_address(&f.program_id, &f.alice_user.key, &bob_user.key).0;
+    (bob_user, TestAccount::empty(follow_address))
+}
+
+#[test]
+fn follow_counts_on_both_users() {
+    let mut f = Fixture::new();
+    let (mut bob_user, mut follow) = bob(&f);
+    set_clock(42);
+
+    run_follow(&mut f, UserInstruction::Follow, &mut bob_user, &mut follow).unwrap();
+
+    assert_eq!(f.alice_user.read::<User>().following_count, 1);
+    assert_eq!(f.alice_user.read::<User>().follower_count, 0);
+    assert_eq!(bob_user.read::<User>().follower_count, 1);
+    assert_eq!(bob_user.read::<User>().following_count, 0);
+    assert_eq!(
+        follow.read::<Follow>(),
+        Follow {
+            follower: f.alice_user.key,
+            followee: bob_user.key,
+            followed_at: 42,
+        }
+    );
+}
+
+#[test]
+fn following_twice_is_rejected() {
+    let mut f = Fixture::new();
+    let (mut bob_user, mut follow) = bob(&f);
+
+    run_follow(&mut f, UserInstruction::Follow, &mut bob_user, &mut follow).unwrap();
+    assert_eq!(
+        run_follow(&mut f, UserInstruction::Follow, &mut bob_user, &mut follow),
+        Err(AnimeNexaError::AlreadyFollowing.into())
+    );
+    assert_eq!(bob_user.read::<User>().follower_count, 1);
+}
+
+#[test]
+fn following_yourself_is_rejected() {
+    let mut f = Fixture::new();
+    let program_id = f.program_id;
+    let mut alice_again = user_account(&program_id, "alice", &f.alice_wallet.key);
+    let follow_address = find_follow_address(&program_id, &alice_again.key, &alice_again.key).0;
+    let mut follow = TestAccount::empty(follow_address);
+
+    assert_eq!(
+        run_follow(
+            &mut f,
+            UserInstruction::Follow,
+            &mut alice_again,
+            &mut follow
+        ),
+        Err(AnimeNexaError::CannotFollowSelf.into())
+    );
+}
+
+#[test]
+fn unfollow_uncounts_on_both_users_and_refunds_the_edge() {
+    let mut f = Fixture::new();
+    let (mut bob_user, mut follow) = bob(&f);
+    let wallet_lamports = f.alice_wallet.lamports;
+
+    run_follow(&mut f, UserInstruction::Follow, &mut bob_user, &mut follow).unwrap();
+    run_follow(
+        &mut f,
+        UserInstruction::Unfollow,
+        &mut bob_user,
+        &mut follow,
+    )
+    .unwrap();
+
+    assert_eq!(f.alice_user.read::<User>().following_count, 0);
+    assert_eq!(bob_user.read::<User>().follower_count, 0);
+    assert_eq!(follow.lamports, 0);
+    assert_eq!(f.alice_wallet.lamports, wallet_lamports);
+
+    // The edge can be created again once removed
+    run_follow(&mut f, UserInstruction::Follow, &mut bob_user, &mut follow).unwrap();
+    assert_eq!(bob_user.read::<User>().follower_count, 1);
+}
+
+#[test]
+fn unfollow_without_following_is_rejected() {
+    let mut f = Fixture::new();
+    let (mut bob_user, mut follow) = bob(&f);
+
+    assert_eq!(
+        run_follow(
+            &mut f,
+            UserInstruction::Unfollow,
+            &mut bob_user,
+            &mut follow
+        ),
+        Err(AnimeNexaError::NotFollowing.into())
+    );
+}
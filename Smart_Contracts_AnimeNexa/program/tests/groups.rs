@@ -0,0 +1,463 @@
+//! Creating groups, member roles, membership changes, ownership transfers and
+//! group messages.
+//!
+//! Alice acts on the group "group-1".
+
+mod common;
+
+use animenexa_program::{
+    error::AnimeNexaError,
+    instruction::UserInstruction,
+    pda::find_group_address,
+    state::{Group, GroupRole},
+};
+use common::{group_account, run, system_program_account, user_account, Fixture, TestAccount};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey, rent::Rent};
+
+// Run a group instruction acting as Alice on `group`, with `member` as the
+// member being acted on, and return the group as stored afterwards
+fn run_group_instruction(
+    f: &mut Fixture,
+    instruction: UserInstruction,
+    group: &mut TestAccount,
+    member: &Pubkey,
+) -> Result<Group, ProgramError> {
+    let program_id = f.program_id;
+    let mut member_account = TestAccount {
+        key: *member,
+        ..TestAccount::new(program_id, vec![])
+    };
+    run(
+        &program_id,
+        &instruction,
+        &mut [
+            &mut f.alice_user,
+            &mut f.alice_wallet,
+            group,
+            &mut member_account,
+        ],
+    )?;
+    Ok(borsh::from_slice(&group.data).unwrap())
+}
+
+// Run CreateGroup for Alice with `members` as the initial members
+fn create_group(
+    f: &mut Fixture,
+    group: &mut TestAccount,
+    members: Vec<&mut TestAccount>,
+) -> Result<(), ProgramError> {
+    let program_id = f.program_id;
+    let mut system_program = system_program_account();
+    let instruction = UserInstruction::CreateGroup {
+        group_id: "group-1".to_string(),
+    };
+    let mut accounts = vec![
+        &mut f.alice_user,
+        &mut f.alice_wallet,
+        group,
+        &mut system_program,
+    ];
+    accounts.extend(members);
+    run(&program_id, &instruction, &mut accounts)
+}
+
+// The address of Alice's group "group-1", before anything was created there
+fn new_group(f: &Fixture) -> TestAccount {
+    TestAccount::empty(find_group_address(&f.program_id, &f.alice_user.key, "group-1").0)
+}
+
+#[test]
+fn create_group_makes_the_creator_owner_and_adds_members() {
+    let mut f = Fixture::new();
+    let mut group = new_group(&f);
+    let mut bob_user = user_account(&f.program_id, "bob", &Pubkey::new_unique());
+    let mut cy_user = user_account(&f.program_id, "cy", &Pubkey::new_unique());
+
+    create_group(&mut f, &mut group, vec![&mut bob_user, &mut cy_user]).unwrap();
+
+    assert_eq!(group.owner, f.program_id);
+    assert_eq!(
+        group.lamports,
+        Rent::default().minimum_balance(group.data.len())
+    );
+    let group: Group = group.read();
+    assert_eq!(group.group_id, "group-1");
+    assert_eq!(group.creator, f.alice_user.key);
+    assert_eq!(group.owner, f.alice_user.key);
+    let members: Vec<_> = group
+        .members
+        .iter()
+        .map(|member| (member.user_id.as_str(), member.user, member.role))
+        .collect();
+    assert_eq!(
+        members,
+        [
+            ("alice", f.alice_user.key, GroupRole::Owner),
+            ("bob", bob_user.key, GroupRole::Member),
+            ("cy", cy_user.key, GroupRole::Member),
+        ]
+    );
+}
+
+#[test]
+fn create_group_rejects_duplicate_initial_members() {
+    let mut f = Fixture::new();
+    let mut group = new_group(&f);
+    let bob_wallet = Pubkey::new_unique();
+    let mut bob_user = user_account(&f.program_id, "bob", &bob_wallet);
+    let mut bob_again = user_account(&f.program_id, "bob", &bob_wallet);
+
+    assert_eq!(
+        create_group(&mut f, &mut group, vec![&mut bob_user, &mut bob_again]),
+        Err(AnimeNexaError::AlreadyGroupMember.into())
+    );
+}
+
+#[test]
+fn create_group_rejects_an_existing_group() {
+    let mut f = Fixture::new();
+    let (alice, bob) = (f.alice_user.key, Pubkey::new_unique());
+    let mut group = group_account(
+        &f.program_id,
+        &alice,
+        &[
+            ("alice", alice, GroupRole::Owner),
+            ("bob", bob, GroupRole::Member),
+        ],
+    );
+    let stored = group.data.clone();
+
+    assert_eq!(
+        create_group(&mut f, &mut group, vec![]),
+        Err(ProgramError::AccountAlreadyInitialized)
+    );
+    assert_eq!(group.data, stored);
+}
+
+#[test]
+fn moderator_cannot_remove_admin() {
+    let mut f = Fixture::new();
+    let (alice, bob, carol) = (f.alice_user.key, Pubkey::new_unique(), Pubkey::new_unique());
+    let mut group = group_account(
+        &f.program_id,
+        &carol,
+        &[
+            ("carol", carol, GroupRole::Owner),
+            ("bob", bob, GroupRole::Admin),
+            ("alice", alice, GroupRole::Moderator),
+        ],
+    );
+    let instruction = UserInstruction::RemoveMember {
+        group_id: "group-1".to_string(),
+    };
+    assert_eq!(
+        run_group_instruction(&mut f, instruction, &mut group, &bob),
+        Err(AnimeNexaError::InsufficientRole.into())
+    );
+}
+
+#[test]
+fn set_role_cannot_promote_to_owner() {
+    let mut f = Fixture::new();
+    let (alice, bob) = (f.alice_user.key, Pubkey::new_unique());
+    let mut group = group_account(
+        &f.program_id,
+        &alice,
+        &[
+            ("alice", alice, GroupRole::Owner),
+            ("bob", bob, GroupRole::Member),
+        ],
+    );
+    let instruction = UserInstruction::SetRole {
+        group_id: "group-1".to_string(),
+        role: GroupRole::Owner,
+    };
+    assert_eq!(
+        run_group_instruction(&mut f, instruction, &mut group, &bob),
+        Err(AnimeNexaError::InsufficientRole.into())
+    );
+}
+
+#[test]
+fn admin_cannot_promote_to_admin() {
+    let mut f = Fixture::new();
+    let (alice, bob, carol) = (f.alice_user.key, Pubkey::new_unique(), Pubkey::new_unique());
+    let mut group = group_account(
+        &f.program_id,
+        &carol,
+        &[
+            ("carol", carol, GroupRole::Owner),
+            ("alice", alice, GroupRole::Admin),
+            ("bob", bob, GroupRole::Member),
+        ],
+    );
+    let instruction = UserInstruction::SetRole {
+        group_id: "group-1".to_string(),
+        role: GroupRole::Admin,
+    };
+    assert_eq!(
+        run_group_instruction(&mut f, instruction, &mut group, &bob),
+        Err(AnimeNexaError::InsufficientRole.into())
+    );
+}
+
+#[test]
+fn admin_can_promote_member_to_moderator() {
+    let mut f = Fixture::new();
+    let (alice, bob, carol) = (f.alice_user.key, Pubkey::new_unique(), Pubkey::new_unique());
+    let mut group = group_account(
+        &f.program_id,
+        &carol,
+        &[
+            ("carol", carol, GroupRole::Owner),
+            ("alice", alice, GroupRole::Admin),
+            ("bob", bob, GroupRole::Member),
+        ],
+    );
+    let instruction = UserInstruction::SetRole {
+        group_id: "group-1".to_string(),
+        role: GroupRole::Moderator,
+    };
+    let group = run_group_instruction(&mut f, instruction, &mut group, &bob).unwrap();
+    assert_eq!(group.member(&bob).unwrap().role, GroupRole::Moderator);
+}
+
+#[test]
+fn member_added_after_a_removal_uses_the_rent_left_in_the_group() {
+    let mut f = Fixture::new();
+    let program_id = f.program_id;
+    let alice = f.alice_user.key;
+    let mut bob_user = user_account(&program_id, "bob", &Pubkey::new_unique());
+    let mut cy_user = user_account(&program_id, "cy", &Pubkey::new_unique());
+    let mut group = group_account(
+        &program_id,
+        &alice,
+        &[
+            ("alice", alice, GroupRole::Owner),
+            ("bob", bob_user.key, GroupRole::Member),
+        ],
+    );
+    // Funded exactly for its size, as CreateGroup leaves it
+    group.lamports = Rent::default().minimum_balance(group.data.len());
+    let funded = group.lamports;
+
+    let remove = UserInstruction::RemoveMember {
+        group_id: "group-1".to_string(),
+    };
+    let add = UserInstruction::AddMember {
+        group_id: "group-1".to_string(),
+    };
+    let mut system_program = system_program_account();
+
+    run(
+        &program_id,
+        &remove,
+        &mut [
+            &mut f.alice_user,
+            &mut f.alice_wallet,
+            &mut group,
+            &mut bob_user,
+        ],
+    )
+    .unwrap();
+    assert_eq!(group.lamports, funded);
+
+    // "cy" takes less room than "bob" did, and nothing of the surplus goes
+    // to the wallet adding them
+    let wallet_lamports = f.alice_wallet.lamports;
+    run(
+        &program_id,
+        &add,
+        &mut [
+            &mut f.alice_user,
+            &mut f.alice_wallet,
+            &mut group,
+            &mut cy_user,
+            &mut system_program,
+        ],
+    )
+    .unwrap();
+    assert_eq!(f.alice_wallet.lamports, wallet_lamports);
+    assert_eq!(group.lamports, funded);
+
+    // Growing past what the group holds, the wallet only pays the shortfall
+    run(
+        &program_id,
+        &add,
+        &mut [
+            &mut f.alice_user,
+            &mut f.alice_wallet,
+            &mut group,
+            &mut bob_user,
+            &mut system_program,
+        ],
+    )
+    .unwrap();
+    let required = Rent::default().minimum_balance(group.data.len());
+    assert_eq!(group.lamports, required);
+    assert_eq!(
+        f.alice_wallet.lamports,
+        wallet_lamports - (required - funded)
+    );
+}
+
+#[test]
+fn owner_cannot_leave_group() {
+    let mut f = Fixture::new();
+    let program_id = f.program_id;
+    let (alice, bob) = (f.alice_user.key, Pubkey::new_unique());
+    let mut group = group_account(
+        &program_id,
+        &alice,
+        &[
+            ("alice", alice, GroupRole::Owner),
+            ("bob", bob, GroupRole::Member),
+        ],
+    );
+    let instruction = UserInstruction::LeaveGroup {
+        group_id: "group-1".to_string(),
+    };
+    let result = run(
+        &program_id,
+        &instruction,
+        &mut [&mut f.alice_user, &mut f.alice_wallet, &mut group],
+    );
+    assert_eq!(result, Err(AnimeNexaError::OwnerCannotLeave.into()));
+}
+
+#[test]
+fn member_cannot_add_members() {
+    let mut f = Fixture::new();
+    let program_id = f.program_id;
+    let (alice, carol) = (f.alice_user.key, Pubkey::new_unique());
+    let mut group = group_account(
+        &program_id,
+        &carol,
+        &[
+            ("carol", carol, GroupRole::Owner),
+            ("alice", alice, GroupRole::Member),
+        ],
+    );
+    let mut bob_user = user_account(&program_id, "bob", &Pubkey::new_unique());
+    let mut system_program = system_program_account();
+
+    let result = run(
+        &program_id,
+        &UserInstruction::AddMember {
+            group_id: "group-1".to_string(),
+        },
+        &mut [
+            &mut f.alice_user,
+            &mut f.alice_wallet,
+            &mut group,
+            &mut bob_user,
+            &mut system_program,
+        ],
+    );
+    assert_eq!(result, Err(AnimeNexaError::InsufficientRole.into()));
+}
+
+#[test]
+fn member_can_leave_group() {
+    let mut f = Fixture::new();
+    let program_id = f.program_id;
+    let (alice, carol) = (f.alice_user.key, Pubkey::new_unique());
+    let mut group = group_account(
+        &program_id,
+        &carol,
+        &[
+            ("carol", carol, GroupRole::Owner),
+            ("alice", alice, GroupRole::Member),
+        ],
+    );
+    let lamports = group.lamports;
+
+    run(
+        &program_id,
+        &UserInstruction::LeaveGroup {
+            group_id: "group-1".to_string(),
+        },
+        &mut [&mut f.alice_user, &mut f.alice_wallet, &mut group],
+    )
+    .unwrap();
+
+    let stored: Group = group.read();
+    assert!(stored.member(&alice).is_none());
+    assert_eq!(stored.members.len(), 1);
+    assert_eq!(stored.owner, carol);
+    // The freed rent stays with the group
+    assert_eq!(group.lamports, lamports);
+}
+
+#[test]
+fn only_owner_can_transfer_ownership() {
+    let mut f = Fixture::new();
+    let (alice, bob, carol) = (f.alice_user.key, Pubkey::new_unique(), Pubkey::new_unique());
+    let mut group = group_account(
+        &f.program_id,
+        &carol,
+        &[
+            ("carol", carol, GroupRole::Owner),
+            ("alice", alice, GroupRole::Admin),
+            ("bob", bob, GroupRole::Member),
+        ],
+    );
+    let instruction = UserInstruction::TransferOwnership {
+        group_id: "group-1".to_string(),
+    };
+    assert_eq!(
+        run_group_instruction(&mut f, instruction, &mut group, &bob),
+        Err(AnimeNexaError::InsufficientRole.into())
+    );
+}
+
+#[test]
+fn transfer_ownership_keeps_previous_owner_as_admin() {
+    let mut f = Fixture::new();
+    let (alice, bob) = (f.alice_user.key, Pubkey::new_unique());
+    let mut group = group_account(
+        &f.program_id,
+        &alice,
+        &[
+            ("alice", alice, GroupRole::Owner),
+            ("bob", bob, GroupRole::Member),
+        ],
+    );
+    let instruction = UserInstruction::TransferOwnership {
+        group_id: "group-1".to_string(),
+    };
+    let group = run_group_instruction(&mut f, instruction, &mut group, &bob).unwrap();
+    assert_eq!(group.owner, bob);
+    assert_eq!(group.creator, alice);
+    assert_eq!(group.member(&bob).unwrap().role, GroupRole::Owner);
+    assert_eq!(group.member(&alice).unwrap().role, GroupRole::Admin);
+}
+
+#[test]
+fn send_group_message_rejects_existing_account() {
+    let mut f = Fixture::new();
+    let program_id = f.program_id;
+    let alice = f.alice_user.key;
+    let mut group = group_account(&program_id, &alice, &[("alice", alice, GroupRole::Owner)]);
+    let mut system_program = system_program_account();
+
+    // Alice tries to write her message over the group itself
+    let mut target = group_account(&program_id, &alice, &[("alice", alice, GroupRole::Owner)]);
+    let result = run(
+        &program_id,
+        &UserInstruction::SendGroupMessage {
+            message_id: "message-1".to_string(),
+            group_id: "group-1".to_string(),
+            user_id: "alice".to_string(),
+            message_content: "hi".to_string(),
+        },
+        &mut [
+            &mut f.alice_user,
+            &mut f.alice_wallet,
+            &mut group,
+            &mut target,
+            &mut system_program,
+        ],
+    );
+    assert_eq!(result, Err(ProgramError::AccountAlreadyInitialized));
+}
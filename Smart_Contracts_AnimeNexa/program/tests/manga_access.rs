@@ -0,0 +1,393 @@
+//! Resolving manga access from series and chapter receipts, and buying chapters.
+//!
+//! Alice reads Bob's manga "manga-1", whose first two chapters are a free
+//! preview.
+
+mod common;
+
+use animenexa_program::{
+    error::AnimeNexaError,
+    instruction::UserInstruction,
+    pda::{
+        find_chapter_address, find_config_address, find_content_address, find_manga_access_address,
+        find_manga_address, find_receipt_address,
+    },
+    state::{
+        AccessGrant, AccessTerms, Chapter, Manga, MangaAccess, MonetizedContent, PlatformConfig,
+        Purchase, ReactionCounts,
+    },
+};
+use common::{run, set_clock, system_program_account, user_account, Fixture, TestAccount};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+const CHAPTER_PRICE: u64 = 1_000;
+
+struct Series {
+    f: Fixture,
+    bob_wallet: TestAccount,
+    bob_user: TestAccount,
+    manga: TestAccount,
+    content: TestAccount,
+}
+
+impl Series {
+    fn new() -> Self {
+        let f = Fixture::new();
+        let program_id = f.program_id;
+        let bob_wallet = TestAccount::wallet(false);
+        let bob_user = user_account(&program_id, "bob", &bob_wallet.key);
+
+        let manga = Manga {
+            manga_id: "manga-1".to_string(),
+            creator_user_id: "bob".to_string(),
+            title: "Manga".to_string(),
+            description: String::new(),
+            timestamp: 0,
+            is_free: false,
+            chapter_count: 10,
+            free_chapters: 2,
+            reactions: ReactionCounts::default(),
+        };
+        let manga_address = find_manga_address(&program_id, &bob_user.key, "manga-1").0;
+
+        let content = MonetizedContent {
+            content_id: "manga-1".to_string(),
+            creator_user_id: "bob".to_string(),
+            price: 10 * CHAPTER_PRICE,
+            payment_mint: None,
+            revenue_shares: vec![],
+            access_terms: AccessTerms::Permanent,
+            total_sales: 0,
+            total_revenue: 0,
+        };
+        let content_address = find_content_address(&program_id, &bob_user.key, "manga-1").0;
+
+        Self {
+            manga: TestAccount::state(&program_id, manga_address, &manga),
+            content: TestAccount::state(&program_id, content_address, &content),
+            f,
+            bob_wallet,
+            bob_user,
+        }
+    }
+
+    fn chapter(&self, chapter_number: u32) -> TestAccount {
+        let chapter = Chapter {
+            manga_id: "manga-1".to_string(),
+            chapter_number,
+            title: format!("Chapter {}", chapter_number),
+            pages: vec![],
+            content_hash: Chapter::hash_pages(&[]),
+            price: CHAPTER_PRICE,
+            published_at: 0,
+            updated_at: 0,
+        };
+        let address = self.chapter_address(chapter_number);
+        TestAccount::state(&self.f.program_id, address, &chapter)
+    }
+
+    fn chapter_address(&self, chapter_number: u32) -> Pubkey {
+        find_chapter_address(&self.f.program_id, &self.manga.key, chapter_number).0
+    }
+
+    // Alice's receipt for the whole series, or an empty address when `grant` is None
+    fn series_receipt(&self, grant: Option<AccessGrant>) -> TestAccount {
+        self.receipt(self.content.key, grant)
+    }
+
+    // Alice's receipt for one chapter, or an empty address when `grant` is None
+    fn chapter_receipt(&self, chapter_number: u32, grant: Option<AccessGrant>) -> TestAccount {
+        self.receipt(self.chapter_address(chapter_number), grant)
+    }
+
+    fn receipt(&self, purchased: Pubkey, grant: Option<AccessGrant>) -> TestAccount {
+        let program_id = &self.f.program_id;
+        let address = find_receipt_address(program_id, &purchased, &self.f.alice_user.key).0;
+        match grant {
+            Some(grant) => {
+                let purchase = Purchase {
+                    content: self.content.key,
+                    content_id: "manga-1".to_string(),
+                    purchaser_user_id: "alice".to_string(),
+                    purchaser: self.f.alice_wallet.key,
+                    amount: CHAPTER_PRICE,
+                    payment_mint: None,
+                    timestamp: 0,
+                    grant,
+                    platform_fee: 0,
+                    payouts: vec![],
+                };
+                TestAccount::state(program_id, address, &purchase)
+            }
+            None => TestAccount::empty(address),
+        }
+    }
+
+    // Run AccessManga for Alice, returning the grant it recorded
+    fn access(
+        &mut self,
+        chapter: Option<u32>,
+        series_receipt: &mut TestAccount,
+        chapter_receipt: &mut TestAccount,
+    ) -> Result<AccessGrant, ProgramError> {
+        let program_id = self.f.program_id;
+        let access_address =
+            find_manga_access_address(&program_id, &self.manga.key, &self.f.alice_user.key).0;
+        let mut access_account = TestAccount::empty(access_address);
+        let mut system_program = system_program_account();
+
+        let instruction = UserInstruction::AccessManga {
+            manga_id: "manga-1".to_string(),
+            user_id: "alice".to_string(),
+            chapter,
+        };
+        let mut accounts = vec![
+            &mut self.f.alice_user,
+            &mut self.f.alice_wallet,
+            &mut access_account,
+            &mut self.manga,
+            &mut self.bob_user,
+            series_receipt,
+            &mut system_program,
+        ];
+        if chapter.is_some() {
+            accounts.push(chapter_receipt);
+        }
+        run(&program_id, &instruction, &mut accounts)?;
+
+        Ok(access_account.read::<MangaAccess>().grant)
+    }
+
+    // Run CheckMangaAccess for Alice
+    fn check(
+        &mut self,
+        chapter: Option<u32>,
+        series_receipt: &mut TestAccount,
+        chapter_receipt: &mut TestAccount,
+    ) -> Result<(), ProgramError> {
+        let program_id = self.f.program_id;
+        let instruction = UserInstruction::CheckMangaAccess {
+            manga_id: "manga-1".to_string(),
+            user_id: "alice".to_string(),
+            chapter,
+        };
+        let mut accounts = vec![
+            &mut self.f.alice_user,
+            &mut self.manga,
+            &mut self.bob_user,
+            series_receipt,
+        ];
+        if chapter.is_some() {
+            accounts.push(chapter_receipt);
+        }
+        run(&program_id, &instruction, &mut accounts)
+    }
+
+    // Run PurchaseChapter for Alice, paying lamports with no platform fee
+    fn purchase_chapter(
+        &mut self,
+        chapter_number: u32,
+        series_receipt: &mut TestAccount,
+    ) -> Result<TestAccount, ProgramError> {
+        let program_id = self.f.program_id;
+        let mut treasury = TestAccount::wallet(false);
+        let config = PlatformConfig {
+            admin: Pubkey::new_unique(),
+            treasury: treasury.key,
+            fee_basis_points: 0,
+        };
+        let mut config_account =
+            TestAccount::state(&program_id, find_config_address(&program_id).0, &config);
+        let mut system_program = system_program_account();
+        let mut chapter = self.chapter(chapter_number);
+        let mut receipt = self.chapter_receipt(chapter_number, None);
+
+        let instruction = UserInstruction::PurchaseChapter {
+            manga_id: "manga-1".to_string(),
+            chapter_number,
+            purchaser_user_id: "alice".to_string(),
+            amount: CHAPTER_PRICE,
+            timestamp: 0,
+        };
+        run(
+            &program_id,
+            &instruction,
+            &mut [
+                &mut self.bob_wallet,
+                &mut self.f.alice_wallet,
+                &mut self.content,
+                &mut self.f.alice_user,
+                &mut self.bob_user,
+                &mut system_program,
+                &mut config_account,
+                &mut treasury,
+                &mut receipt,
+                &mut self.manga,
+                &mut chapter,
+                series_receipt,
+            ],
+        )?;
+
+        Ok(receipt)
+    }
+}
+
+#[test]
+fn series_purchase_grants_every_chapter() {
+    let mut s = Series::new();
+    let mut series_receipt = s.series_receipt(Some(AccessGrant::Permanent));
+    let mut chapter_receipt = s.chapter_receipt(7, None);
+
+    assert_eq!(
+        s.access(None, &mut series_receipt, &mut chapter_receipt),
+        Ok(AccessGrant::Permanent)
+    );
+    assert_eq!(
+        s.check(None, &mut series_receipt, &mut chapter_receipt),
+        Ok(())
+    );
+    assert_eq!(
+        s.check(Some(7), &mut series_receipt, &mut chapter_receipt),
+        Ok(())
+    );
+}
+
+#[test]
+fn free_preview_grants_only_the_first_chapters() {
+    let mut s = Series::new();
+    let mut series_receipt = s.series_receipt(None);
+    let mut chapter_receipt = s.chapter_receipt(2, None);
+
+    let preview = AccessGrant::ChapterRange {
+        first_chapter: 1,
+        last_chapter: 2,
+    };
+    assert_eq!(
+        s.access(Some(2), &mut series_receipt, &mut chapter_receipt),
+        Ok(preview)
+    );
+    assert_eq!(
+        s.access(None, &mut series_receipt, &mut chapter_receipt),
+        Ok(preview)
+    );
+
+    let mut chapter_receipt = s.chapter_receipt(3, None);
+    assert_eq!(
+        s.check(Some(3), &mut series_receipt, &mut chapter_receipt),
+        Err(AnimeNexaError::MangaNotPurchased.into())
+    );
+}
+
+#[test]
+fn chapter_receipts_grant_their_own_chapter() {
+    let mut s = Series::new();
+    let mut series_receipt = s.series_receipt(None);
+    let chapter_grant = |chapter| AccessGrant::ChapterRange {
+        first_chapter: chapter,
+        last_chapter: chapter,
+    };
+    let mut receipt_5 = s.chapter_receipt(5, Some(chapter_grant(5)));
+    let mut receipt_6 = s.chapter_receipt(6, Some(chapter_grant(6)));
+
+    // Recording access to chapter 6 must not hide the purchase of chapter 5
+    assert_eq!(
+        s.access(Some(6), &mut series_receipt, &mut receipt_6),
+        Ok(chapter_grant(6))
+    );
+    assert_eq!(
+        s.check(Some(5), &mut series_receipt, &mut receipt_5),
+        Ok(())
+    );
+    assert_eq!(
+        s.check(Some(6), &mut series_receipt, &mut receipt_6),
+        Ok(())
+    );
+
+    // A receipt only counts for the chapter it was bought for
+    assert_eq!(
+        s.check(Some(7), &mut series_receipt, &mut receipt_6),
+        Err(AnimeNexaError::MangaNotPurchased.into())
+    );
+}
+
+#[test]
+fn expired_rental_no_longer_grants_access() {
+    let mut s = Series::new();
+    let mut series_receipt = s.series_receipt(Some(AccessGrant::Rental { expires_at: 1_000 }));
+    let mut chapter_receipt = s.chapter_receipt(5, None);
+
+    set_clock(999);
+    assert_eq!(
+        s.access(Some(5), &mut series_receipt, &mut chapter_receipt),
+        Ok(AccessGrant::Rental { expires_at: 1_000 })
+    );
+
+    set_clock(1_000);
+    assert_eq!(
+        s.check(Some(5), &mut series_receipt, &mut chapter_receipt),
+        Err(AnimeNexaError::AccessExpired.into())
+    );
+    assert_eq!(
+        s.access(Some(5), &mut series_receipt, &mut chapter_receipt),
+        Err(AnimeNexaError::AccessExpired.into())
+    );
+
+    // The free preview outlives the rental
+    let mut chapter_receipt = s.chapter_receipt(1, None);
+    assert_eq!(
+        s.check(Some(1), &mut series_receipt, &mut chapter_receipt),
+        Ok(())
+    );
+}
+
+#[test]
+fn purchase_chapter_rejects_chapter_covered_by_the_series() {
+    let mut s = Series::new();
+    let mut series_receipt = s.series_receipt(Some(AccessGrant::Permanent));
+
+    assert_eq!(
+        s.purchase_chapter(5, &mut series_receipt).err(),
+        Some(AnimeNexaError::AlreadyPurchased.into())
+    );
+}
+
+#[test]
+fn purchase_chapter_charges_once_the_rental_expired() {
+    let mut s = Series::new();
+    let mut series_receipt = s.series_receipt(Some(AccessGrant::Rental { expires_at: 1_000 }));
+
+    set_clock(999);
+    assert_eq!(
+        s.purchase_chapter(5, &mut series_receipt).err(),
+        Some(AnimeNexaError::AlreadyPurchased.into())
+    );
+
+    set_clock(1_000);
+    let alice_lamports = s.f.alice_wallet.lamports;
+    let bob_lamports = s.bob_wallet.lamports;
+    let receipt = s.purchase_chapter(5, &mut series_receipt).unwrap();
+
+    assert_eq!(
+        receipt.read::<Purchase>().grant,
+        AccessGrant::ChapterRange {
+            first_chapter: 5,
+            last_chapter: 5,
+        }
+    );
+    assert_eq!(s.bob_wallet.lamports, bob_lamports + CHAPTER_PRICE);
+    assert_eq!(
+        s.f.alice_wallet.lamports,
+        alice_lamports - CHAPTER_PRICE - receipt.lamports
+    );
+}
+
+#[test]
+fn purchase_chapter_rejects_another_series_receipt() {
+    let mut s = Series::new();
+    let mut series_receipt = TestAccount::empty(Pubkey::new_unique());
+
+    assert_eq!(
+        s.purchase_chapter(5, &mut series_receipt).err(),
+        Some(ProgramError::InvalidSeeds)
+    );
+}
@@ -0,0 +1,138 @@
+//! Attaching media to posts.
+//!
+//! Alice attaches media to her post "post-1".
+
+mod common;
+
+use animenexa_program::{
+    error::AnimeNexaError,
+    instruction::UserInstruction,
+    pda::find_media_address,
+    state::{post::MAX_POST_MEDIA, Media, MediaLocator, MediaReference, MediaType, Post},
+};
+use common::{post, post_account, run, system_program_account, user_account, Fixture, TestAccount};
+use solana_program::{program_error::ProgramError, rent::Rent};
+
+fn image() -> MediaReference {
+    MediaReference {
+        locator: MediaLocator::Url("https://example.com/a.png".to_string()),
+        sha256: [0; 32],
+        byte_size: 1024,
+        mime_type: "image/png".to_string(),
+    }
+}
+
+// Run UploadMedia for Alice, attaching "media-1" to `post`
+fn upload(
+    f: &mut Fixture,
+    post: &mut TestAccount,
+    media: &mut TestAccount,
+    payer: &mut TestAccount,
+) -> Result<(), ProgramError> {
+    let program_id = f.program_id;
+    let mut system_program = system_program_account();
+    let instruction = UserInstruction::UploadMedia {
+        media_id: "media-1".to_string(),
+        post_id: "post-1".to_string(),
+        user_id: "alice".to_string(),
+        reference: image(),
+        media_type: MediaType::Image,
+    };
+    run(
+        &program_id,
+        &instruction,
+        &mut [
+            &mut f.alice_user,
+            &mut f.alice_wallet,
+            post,
+            media,
+            payer,
+            &mut system_program,
+        ],
+    )
+}
+
+// The address of Alice's media "media-1", before anything was created there
+fn new_media(f: &Fixture) -> TestAccount {
+    TestAccount::empty(find_media_address(&f.program_id, &f.alice_user.key, "media-1").0)
+}
+
+#[test]
+fn upload_media_creates_the_media_and_attaches_it() {
+    let mut f = Fixture::new();
+    let mut alice_post = post_account(&f.program_id, &f.alice_user, &post("post-1", "alice", ""));
+    let post_len = alice_post.data.len();
+    let mut media = new_media(&f);
+    let mut payer = TestAccount::wallet(true);
+
+    upload(&mut f, &mut alice_post, &mut media, &mut payer).unwrap();
+
+    assert_eq!(media.owner, f.program_id);
+    assert_eq!(
+        media.read::<Media>(),
+        Media {
+            media_id: "media-1".to_string(),
+            post_id: "post-1".to_string(),
+            user_id: "alice".to_string(),
+            reference: image(),
+            media_type: MediaType::Image,
+        }
+    );
+
+    // The post grows by the new ID and stays rent-exempt
+    assert_eq!(alice_post.read::<Post>().media_ids, ["media-1"]);
+    assert_eq!(alice_post.data.len(), post_len + 4 + "media-1".len());
+    assert_eq!(
+        alice_post.lamports,
+        Rent::default().minimum_balance(alice_post.data.len())
+    );
+}
+
+#[test]
+fn upload_media_rejects_an_attachment_over_the_limit() {
+    let mut f = Fixture::new();
+    let mut full = post("post-1", "alice", "");
+    full.media_ids = (0..MAX_POST_MEDIA)
+        .map(|n| format!("media-{}", n))
+        .collect();
+    let mut alice_post = post_account(&f.program_id, &f.alice_user, &full);
+    let mut media = new_media(&f);
+    let mut payer = TestAccount::wallet(true);
+
+    assert_eq!(
+        upload(&mut f, &mut alice_post, &mut media, &mut payer),
+        Err(AnimeNexaError::TooManyMedia.into())
+    );
+}
+
+#[test]
+fn upload_media_rejects_deleted_post() {
+    let mut f = Fixture::new();
+    let mut deleted = post("post-1", "alice", "");
+    deleted.deleted = true;
+    let mut alice_post = post_account(&f.program_id, &f.alice_user, &deleted);
+    let mut media = new_media(&f);
+    let mut payer = TestAccount::wallet(true);
+
+    assert_eq!(
+        upload(&mut f, &mut alice_post, &mut media, &mut payer),
+        Err(AnimeNexaError::PostDeleted.into())
+    );
+}
+
+#[test]
+fn upload_media_rejects_another_users_post() {
+    let mut f = Fixture::new();
+    let program_id = f.program_id;
+    let bob_wallet = TestAccount::wallet(false);
+    let bob_user = user_account(&program_id, "bob", &bob_wallet.key);
+    let mut bob_post = post_account(&program_id, &bob_user, &post("post-1", "bob", ""));
+    let mut media = new_media(&f);
+    let mut payer = TestAccount::wallet(true);
+
+    // Alice signs for herself but attaches media to Bob's post
+    assert_eq!(
+        upload(&mut f, &mut bob_post, &mut media, &mut payer),
+        Err(AnimeNexaError::Unauthorized.into())
+    );
+}
@@ -0,0 +1,113 @@
+//! Splitting a purchase between the platform, contributors and the creator.
+
+use animenexa_program::{
+    payment::{split_amount, Split},
+    state::{ContributorRole, RevenueShare},
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+fn share(role: ContributorRole, basis_points: u16) -> RevenueShare {
+    RevenueShare {
+        wallet: Pubkey::new_unique(),
+        role,
+        basis_points,
+    }
+}
+
+#[test]
+fn split_without_fee_or_shares_pays_the_creator() {
+    assert_eq!(
+        split_amount(1_000, 0, &[]),
+        Ok(Split {
+            platform_fee: 0,
+            share_amounts: vec![],
+            creator_amount: 1_000,
+        })
+    );
+}
+
+#[test]
+fn split_takes_the_fee_before_the_shares() {
+    // 2.5% fee, then 20% of the remaining 975 to the artist
+    let shares = [share(ContributorRole::Artist, 2_000)];
+    assert_eq!(
+        split_amount(1_000, 250, &shares),
+        Ok(Split {
+            platform_fee: 25,
+            share_amounts: vec![195],
+            creator_amount: 780,
+        })
+    );
+}
+
+#[test]
+fn split_rounds_the_fee_down() {
+    // 1% of 99 is 0.99, which rounds down to nothing
+    let split = split_amount(99, 100, &[]).unwrap();
+    assert_eq!(split.platform_fee, 0);
+    assert_eq!(split.creator_amount, 99);
+}
+
+#[test]
+fn split_gives_rounding_dust_to_the_creator() {
+    // Three thirds of 100 round down to 33 each; the leftover unit stays with the creator
+    let shares = [
+        share(ContributorRole::Artist, 3_333),
+        share(ContributorRole::Writer, 3_333),
+        share(ContributorRole::Translator, 3_334),
+    ];
+    let split = split_amount(100, 0, &shares).unwrap();
+    assert_eq!(split.share_amounts, vec![33, 33, 33]);
+    assert_eq!(split.creator_amount, 1);
+}
+
+#[test]
+fn split_with_full_shares_leaves_the_creator_only_dust() {
+    let shares = [
+        share(ContributorRole::Artist, 5_000),
+        share(ContributorRole::Writer, 5_000),
+    ];
+    let split = split_amount(1_001, 0, &shares).unwrap();
+    assert_eq!(split.share_amounts, vec![500, 500]);
+    assert_eq!(split.creator_amount, 1);
+}
+
+#[test]
+fn split_with_full_fee_goes_to_the_platform() {
+    let shares = [share(ContributorRole::Artist, 5_000)];
+    assert_eq!(
+        split_amount(1_000, 10_000, &shares),
+        Ok(Split {
+            platform_fee: 1_000,
+            share_amounts: vec![0],
+            creator_amount: 0,
+        })
+    );
+}
+
+#[test]
+fn split_always_adds_up_to_the_amount() {
+    let shares = [
+        share(ContributorRole::Artist, 1_234),
+        share(ContributorRole::Other, 4_321),
+    ];
+    for amount in [0, 1, 7, 999, 123_456_789, u64::MAX] {
+        let split = split_amount(amount, 375, &shares).unwrap();
+        let total = split.platform_fee as u128
+            + split.share_amounts.iter().map(|a| *a as u128).sum::<u128>()
+            + split.creator_amount as u128;
+        assert_eq!(total, amount as u128);
+    }
+}
+
+#[test]
+fn split_rejects_shares_over_the_whole() {
+    let shares = [
+        share(ContributorRole::Artist, 6_000),
+        share(ContributorRole::Writer, 6_000),
+    ];
+    assert_eq!(
+        split_amount(1_000, 0, &shares),
+        Err(ProgramError::ArithmeticOverflow)
+    );
+}
@@ -0,0 +1,634 @@
+//! Creating, editing, deleting and reposting posts.
+
+mod common;
+
+use animenexa_program::{
+    error::AnimeNexaError,
+    event::PostEvent,
+    instruction::UserInstruction,
+    pda::{find_media_address, find_post_address, find_repost_address, find_username_address},
+    state::{
+        ContentWarning, Media, MediaLocator, MediaReference, MediaType, Post, PostContent,
+        PostVisibility, RepostRecord, UsernameRecord,
+    },
+};
+use common::{
+    post, post_account, run, system_program_account, take_logged_data, user_account, Fixture,
+    TestAccount,
+};
+use solana_program::{hash::hash, program_error::ProgramError, pubkey::Pubkey, rent::Rent};
+
+// Run CreatePost for Alice into her post PDA, followed by `registry` entries
+fn create(
+    f: &mut Fixture,
+    content: PostContent,
+    registry: &mut [TestAccount],
+) -> Result<(), ProgramError> {
+    let program_id = f.program_id;
+    let mut post_account = TestAccount {
+        key: find_post_address(&program_id, &f.alice_user.key, "post-1").0,
+        ..TestAccount::wallet(false)
+    };
+    let mut payer = TestAccount::wallet(true);
+    let mut system_program = system_program_account();
+    let instruction = UserInstruction::CreatePost {
+        post_id: "post-1".to_string(),
+        user_id: "alice".to_string(),
+        content,
+        timestamp: 0,
+        visibility: PostVisibility::Public,
+    };
+
+    let mut accounts: Vec<&mut TestAccount> = vec![
+        &mut f.alice_user,
+        &mut f.alice_wallet,
+        &mut post_account,
+        &mut payer,
+        &mut system_program,
+    ];
+    accounts.extend(registry.iter_mut());
+    run(&program_id, &instruction, &mut accounts)
+}
+
+fn edit(f: &mut Fixture, post: &mut TestAccount, content: PostContent) -> Result<(), ProgramError> {
+    edit_with_registry(f, post, content, &mut [])
+}
+
+fn edit_with_registry(
+    f: &mut Fixture,
+    post: &mut TestAccount,
+    content: PostContent,
+    registry: &mut [TestAccount],
+) -> Result<(), ProgramError> {
+    let program_id = f.program_id;
+    let mut system_program = system_program_account();
+    let instruction = UserInstruction::EditPost {
+        post_id: "post-1".to_string(),
+        content,
+    };
+    let mut accounts: Vec<&mut TestAccount> = vec![
+        &mut f.alice_user,
+        &mut f.alice_wallet,
+        post,
+        &mut system_program,
+    ];
+    accounts.extend(registry.iter_mut());
+    run(&program_id, &instruction, &mut accounts)
+}
+
+// Run DeletePost for Alice's "post-1", followed by `rest`
+fn delete(
+    f: &mut Fixture,
+    post: &mut TestAccount,
+    rest: &mut [TestAccount],
+) -> Result<(), ProgramError> {
+    let program_id = f.program_id;
+    let mut system_program = system_program_account();
+    let instruction = UserInstruction::DeletePost {
+        post_id: "post-1".to_string(),
+    };
+    let mut accounts: Vec<&mut TestAccount> = vec![
+        &mut f.alice_user,
+        &mut f.alice_wallet,
+        post,
+        &mut system_program,
+    ];
+    accounts.extend(rest.iter_mut());
+    run(&program_id, &instruction, &mut accounts)
+}
+
+fn text(text: &str) -> PostContent {
+    PostContent {
+        text: text.to_string(),
+        ..PostContent::default()
+    }
+}
+
+fn mentioning(username: &str) -> PostContent {
+    PostContent {
+        text: format!("hi @{}", username),
+        mentions: vec![username.to_string()],
+        ..PostContent::default()
+    }
+}
+
+// Bob's post "original", and Alice's record of her reposts of it
+struct Original {
+    bob_user: TestAccount,
+    post: TestAccount,
+    record: TestAccount,
+}
+
+impl Original {
+    fn new(f: &Fixture) -> Self {
+        let bob_user = user_account(&f.program_id, "bob", &Pubkey::new_unique());
+        let post = post_account(&f.program_id, &bob_user, &post("original", "bob", "hello"));
+        let record =
+            TestAccount::empty(find_repost_address(&f.program_id, &post.key, &f.alice_user.key).0);
+        Self {
+            bob_user,
+            post,
+            record,
+        }
+    }
+}
+
+// Run Repost for Alice of the original as her public "post-1", returning her repost
+fn repost(
+    f: &mut Fixture,
+    original: &mut Original,
+    quote: Option<PostContent>,
+) -> Result<TestAccount, ProgramError> {
+    repost_as(f, original, "post-1", quote, PostVisibility::Public)
+}
+
+fn repost_as(
+    f: &mut Fixture,
+    original: &mut Original,
+    post_id: &str,
+    quote: Option<PostContent>,
+    visibility: PostVisibility,
+) -> Result<TestAccount, ProgramError> {
+    let program_id = f.program_id;
+    let mut repost =
+        TestAccount::empty(find_post_address(&program_id, &f.alice_user.key, post_id).0);
+    let mut system_program = system_program_account();
+    let instruction = UserInstruction::Repost {
+        post_id: post_id.to_string(),
+        user_id: "alice".to_string(),
+        quote,
+        timestamp: 0,
+        visibility,
+    };
+    run(
+        &program_id,
+        &instruction,
+        &mut [
+            &mut f.alice_user,
+            &mut f.alice_wallet,
+            &mut repost,
+            &mut original.post,
+            &mut original.bob_user,
+            &mut original.record,
+            &mut system_program,
+        ],
+    )?;
+
+    Ok(repost)
+}
+
+// Run DeletePost for Alice's `repost` of the original
+fn delete_repost(
+    f: &mut Fixture,
+    repost: &mut TestAccount,
+    original: &mut Original,
+) -> Result<(), ProgramError> {
+    let program_id = f.program_id;
+    let mut system_program = system_program_account();
+    let instruction = UserInstruction::DeletePost {
+        post_id: repost.read::<Post>().post_id,
+    };
+    run(
+        &program_id,
+        &instruction,
+        &mut [
+            &mut f.alice_user,
+            &mut f.alice_wallet,
+            repost,
+            &mut system_program,
+            &mut original.post,
+            &mut original.record,
+        ],
+    )
+}
+
+// Alice's "post-1" with the given media attached, and the media accounts
+fn post_with_media(f: &Fixture, media_ids: &[&str]) -> (TestAccount, Vec<TestAccount>) {
+    let program_id = f.program_id;
+    let mut alice_post = post("post-1", "alice", "hello");
+    alice_post.media_ids = media_ids.iter().map(|id| id.to_string()).collect();
+
+    let media = media_ids
+        .iter()
+        .map(|media_id| {
+            let media = Media {
+                media_id: media_id.to_string(),
+                post_id: "post-1".to_string(),
+                user_id: "alice".to_string(),
+                reference: MediaReference {
+                    locator: MediaLocator::Url(format!("https://example.com/{}.png", media_id)),
+                    sha256: [0; 32],
+                    byte_size: 1024,
+                    mime_type: "image/png".to_string(),
+                },
+                media_type: MediaType::Image,
+            };
+            let address = find_media_address(&program_id, &f.alice_user.key, media_id).0;
+            TestAccount::state(&program_id, address, &media)
+        })
+        .collect();
+
+    (post_account(&program_id, &f.alice_user, &alice_post), media)
+}
+
+#[test]
+fn edit_post_bumps_revision_and_keeps_previous_content_hash() {
+    let mut f = Fixture::new();
+    let mut alice_post = post_account(&f.program_id, &f.alice_user, &post("post-1", "alice", "hi"));
+
+    edit(&mut f, &mut alice_post, text("hello")).unwrap();
+    let edited: Post = alice_post.read();
+    assert_eq!(edited.content, text("hello"));
+    assert_eq!(edited.revision, 1);
+    assert_eq!(
+        edited.previous_content_hash,
+        Some(hash(&borsh::to_vec(&text("hi")).unwrap()).to_bytes())
+    );
+
+    edit(&mut f, &mut alice_post, text("hello, world")).unwrap();
+    let edited: Post = alice_post.read();
+    assert_eq!(edited.revision, 2);
+    assert_eq!(
+        edited.previous_content_hash,
+        Some(hash(&borsh::to_vec(&text("hello")).unwrap()).to_bytes())
+    );
+}
+
+#[test]
+fn edit_post_emits_mention_and_hashtag_events() {
+    let mut f = Fixture::new();
+    let program_id = f.program_id;
+    let mut alice_post = post_account(&program_id, &f.alice_user, &post("post-1", "alice", "hi"));
+    let bob_wallet = TestAccount::wallet(false);
+    let bob_user = user_account(&program_id, "bob", &bob_wallet.key);
+    let record = UsernameRecord {
+        username: "bob".to_string(),
+        owner: bob_wallet.key,
+        user_account: bob_user.key,
+    };
+    let mut registry = [TestAccount::state(
+        &program_id,
+        find_username_address(&program_id, "bob").0,
+        &record,
+    )];
+
+    let content = PostContent {
+        text: "hi @Bob #anime".to_string(),
+        mentions: vec!["Bob".to_string()],
+        hashtags: vec!["anime".to_string()],
+        ..PostContent::default()
+    };
+    take_logged_data();
+    edit_with_registry(&mut f, &mut alice_post, content, &mut registry).unwrap();
+
+    let events: Vec<PostEvent> = take_logged_data()
+        .iter()
+        .map(|data| borsh::from_slice(data).unwrap())
+        .collect();
+    assert_eq!(
+        events,
+        vec![
+            PostEvent::Mention {
+                post: alice_post.key,
+                author: f.alice_user.key,
+                mentioned_user: bob_user.key,
+                username: "bob".to_string(),
+            },
+            PostEvent::Hashtag {
+                post: alice_post.key,
+                author: f.alice_user.key,
+                hashtag: "anime".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn edit_post_rejects_deleted_post() {
+    let mut f = Fixture::new();
+    let mut deleted = post("post-1", "alice", "");
+    deleted.deleted = true;
+    let mut alice_post = post_account(&f.program_id, &f.alice_user, &deleted);
+
+    assert_eq!(
+        edit(&mut f, &mut alice_post, text("back")),
+        Err(AnimeNexaError::PostDeleted.into())
+    );
+}
+
+#[test]
+fn delete_post_tombstones_the_post_and_closes_its_media() {
+    let mut f = Fixture::new();
+    let (mut alice_post, mut media) = post_with_media(&f, &["media-1", "media-2"]);
+    let freed: u64 = media.iter().map(|media| media.lamports).sum();
+    let wallet_lamports = f.alice_wallet.lamports;
+    let post_lamports = alice_post.lamports;
+
+    delete(&mut f, &mut alice_post, &mut media).unwrap();
+
+    let deleted: Post = alice_post.read();
+    assert!(deleted.deleted);
+    assert_eq!(deleted.content, PostContent::default());
+    assert!(deleted.media_ids.is_empty());
+    assert_eq!(deleted.previous_content_hash, None);
+
+    for media in &media {
+        assert_eq!(media.lamports, 0);
+        assert!(media.data.is_empty());
+        assert_eq!(media.owner, solana_program::system_program::id());
+    }
+
+    // The wallet gets back the media rent and what the shrunk post no longer needs
+    assert_eq!(
+        alice_post.lamports,
+        Rent::default().minimum_balance(alice_post.data.len())
+    );
+    assert_eq!(
+        f.alice_wallet.lamports,
+        wallet_lamports + freed + post_lamports - alice_post.lamports
+    );
+
+    // Deleting the tombstone again is refused
+    assert_eq!(
+        delete(&mut f, &mut alice_post, &mut []),
+        Err(AnimeNexaError::PostDeleted.into())
+    );
+}
+
+#[test]
+fn delete_post_rejects_media_accounts_out_of_order() {
+    let mut f = Fixture::new();
+    let (mut alice_post, mut media) = post_with_media(&f, &["media-1", "media-2"]);
+    media.reverse();
+
+    assert_eq!(
+        delete(&mut f, &mut alice_post, &mut media),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert!(!alice_post.read::<Post>().deleted);
+}
+
+#[test]
+fn delete_post_rejects_media_account_not_owned_by_the_program() {
+    let mut f = Fixture::new();
+    let (mut alice_post, mut media) = post_with_media(&f, &["media-1"]);
+    media[0].owner = Pubkey::new_unique();
+
+    assert_eq!(
+        delete(&mut f, &mut alice_post, &mut media),
+        Err(ProgramError::InvalidArgument)
+    );
+}
+
+#[test]
+fn delete_post_requires_every_media_account() {
+    let mut f = Fixture::new();
+    let (mut alice_post, mut media) = post_with_media(&f, &["media-1", "media-2"]);
+    media.pop();
+
+    assert_eq!(
+        delete(&mut f, &mut alice_post, &mut media),
+        Err(ProgramError::NotEnoughAccountKeys)
+    );
+}
+
+#[test]
+fn repost_counts_on_the_original() {
+    let mut f = Fixture::new();
+    let mut original = Original::new(&f);
+
+    let repost: Post = repost(&mut f, &mut original, Some(text("so true")))
+        .unwrap()
+        .read();
+
+    assert_eq!(repost.repost_of, Some(original.post.key));
+    assert_eq!(repost.content, text("so true"));
+    assert_eq!(original.post.read::<Post>().repost_count, 1);
+    assert_eq!(
+        original.record.read::<RepostRecord>(),
+        RepostRecord {
+            user: f.alice_user.key,
+            original: original.post.key,
+            reposts: 1,
+        }
+    );
+}
+
+#[test]
+fn reposting_again_counts_the_user_once() {
+    let mut f = Fixture::new();
+    let mut original = Original::new(&f);
+
+    repost(&mut f, &mut original, None).unwrap();
+    repost_as(
+        &mut f,
+        &mut original,
+        "post-2",
+        None,
+        PostVisibility::Public,
+    )
+    .unwrap();
+    repost_as(
+        &mut f,
+        &mut original,
+        "post-3",
+        Some(text("still true")),
+        PostVisibility::Public,
+    )
+    .unwrap();
+
+    assert_eq!(original.post.read::<Post>().repost_count, 1);
+    assert_eq!(original.record.read::<RepostRecord>().reposts, 3);
+}
+
+#[test]
+fn repost_is_no_more_visible_than_the_original() {
+    let mut f = Fixture::new();
+    let mut original = Original::new(&f);
+    let mut followers_only: Post = original.post.read();
+    followers_only.visibility = PostVisibility::FollowersOnly;
+    original.post.data = borsh::to_vec(&followers_only).unwrap();
+
+    let repost: Post = repost(&mut f, &mut original, None).unwrap().read();
+
+    assert_eq!(repost.visibility, PostVisibility::FollowersOnly);
+}
+
+#[test]
+fn repost_of_a_public_post_keeps_the_chosen_visibility() {
+    let mut f = Fixture::new();
+    let mut original = Original::new(&f);
+
+    let repost: Post = repost_as(
+        &mut f,
+        &mut original,
+        "post-1",
+        None,
+        PostVisibility::FollowersOnly,
+    )
+    .unwrap()
+    .read();
+
+    assert_eq!(repost.visibility, PostVisibility::FollowersOnly);
+}
+
+#[test]
+fn reposting_a_deleted_post_is_rejected() {
+    let mut f = Fixture::new();
+    let mut original = Original::new(&f);
+    let mut deleted: Post = original.post.read();
+    deleted.deleted = true;
+    original.post.data = borsh::to_vec(&deleted).unwrap();
+
+    assert_eq!(
+        repost(&mut f, &mut original, None).err(),
+        Some(AnimeNexaError::PostDeleted.into())
+    );
+}
+
+#[test]
+fn deleting_a_repost_uncounts_it_on_the_original() {
+    let mut f = Fixture::new();
+    let mut original = Original::new(&f);
+    let mut alice_repost = repost(&mut f, &mut original, None).unwrap();
+    let wallet_lamports = f.alice_wallet.lamports;
+    let record_rent = original.record.lamports;
+
+    delete_repost(&mut f, &mut alice_repost, &mut original).unwrap();
+
+    assert!(alice_repost.read::<Post>().deleted);
+    assert_eq!(original.post.read::<Post>().repost_count, 0);
+    assert_eq!(original.record.lamports, 0);
+    assert_eq!(f.alice_wallet.lamports, wallet_lamports + record_rent);
+}
+
+#[test]
+fn deleting_one_of_several_reposts_keeps_the_user_counted() {
+    let mut f = Fixture::new();
+    let mut original = Original::new(&f);
+    let mut first = repost(&mut f, &mut original, None).unwrap();
+    let mut second = repost_as(
+        &mut f,
+        &mut original,
+        "post-2",
+        None,
+        PostVisibility::Public,
+    )
+    .unwrap();
+
+    delete_repost(&mut f, &mut first, &mut original).unwrap();
+    assert_eq!(original.post.read::<Post>().repost_count, 1);
+    assert_eq!(original.record.read::<RepostRecord>().reposts, 1);
+
+    delete_repost(&mut f, &mut second, &mut original).unwrap();
+    assert_eq!(original.post.read::<Post>().repost_count, 0);
+    assert_eq!(original.record.lamports, 0);
+}
+
+#[test]
+fn deleting_a_repost_of_a_deleted_post_still_uncounts_it() {
+    let mut f = Fixture::new();
+    let mut original = Original::new(&f);
+    let mut alice_repost = repost(&mut f, &mut original, None).unwrap();
+
+    // Bob deletes the original after Alice reposted it
+    let mut tombstone: Post = original.post.read();
+    tombstone.deleted = true;
+    original.post.data = borsh::to_vec(&tombstone).unwrap();
+
+    delete_repost(&mut f, &mut alice_repost, &mut original).unwrap();
+
+    let original: Post = original.post.read();
+    assert!(original.deleted);
+    assert_eq!(original.repost_count, 0);
+}
+
+#[test]
+fn deleting_a_repost_rejects_another_original() {
+    let mut f = Fixture::new();
+    let mut original = Original::new(&f);
+    let mut alice_repost = repost(&mut f, &mut original, None).unwrap();
+    let mut other = Original {
+        post: post_account(
+            &f.program_id,
+            &original.bob_user,
+            &post("other", "bob", "hi"),
+        ),
+        ..Original::new(&f)
+    };
+
+    assert_eq!(
+        delete_repost(&mut f, &mut alice_repost, &mut other),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(original.post.read::<Post>().repost_count, 1);
+}
+
+#[test]
+fn deleting_a_repost_rejects_another_record() {
+    let mut f = Fixture::new();
+    let mut original = Original::new(&f);
+    let mut alice_repost = repost(&mut f, &mut original, None).unwrap();
+    // A record at another address, claiming to hold Alice's only repost
+    let forged = RepostRecord {
+        user: f.alice_user.key,
+        original: original.post.key,
+        reposts: 1,
+    };
+    original.record = TestAccount::state(&f.program_id, Pubkey::new_unique(), &forged);
+
+    assert_eq!(
+        delete_repost(&mut f, &mut alice_repost, &mut original),
+        Err(ProgramError::InvalidArgument)
+    );
+    assert_eq!(original.post.read::<Post>().repost_count, 1);
+}
+
+#[test]
+fn create_post_rejects_duplicate_content_warnings() {
+    let mut f = Fixture::new();
+    let content = PostContent {
+        content_warnings: vec![ContentWarning::Spoiler, ContentWarning::Spoiler],
+        ..PostContent::default()
+    };
+    assert_eq!(
+        create(&mut f, content, &mut []),
+        Err(AnimeNexaError::InvalidPostContent.into())
+    );
+}
+
+#[test]
+fn create_post_requires_registry_account_per_mention() {
+    let mut f = Fixture::new();
+    assert_eq!(
+        create(&mut f, mentioning("bob"), &mut []),
+        Err(ProgramError::NotEnoughAccountKeys)
+    );
+}
+
+#[test]
+fn create_post_rejects_registry_account_of_another_username() {
+    let mut f = Fixture::new();
+    let mut registry = [TestAccount {
+        key: find_username_address(&f.program_id, "carol").0,
+        ..TestAccount::new(f.program_id, vec![])
+    }];
+    assert_eq!(
+        create(&mut f, mentioning("bob"), &mut registry),
+        Err(ProgramError::InvalidSeeds)
+    );
+}
+
+#[test]
+fn create_post_rejects_unregistered_mention() {
+    let mut f = Fixture::new();
+    // The registry PDA for "bob" exists as an address but was never created
+    let mut registry = [TestAccount {
+        key: find_username_address(&f.program_id, "Bob").0,
+        ..TestAccount::wallet(false)
+    }];
+    assert_eq!(
+        create(&mut f, mentioning("Bob"), &mut registry),
+        Err(AnimeNexaError::UnknownMention.into())
+    );
+}
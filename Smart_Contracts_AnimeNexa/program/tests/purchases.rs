@@ -0,0 +1,463 @@
+//! Buying monetized content: receipts, sale counters, rental renewals, and
+//! splitting payments in lamports or SPL tokens.
+//!
+//! Alice buys Bob's content "content-1".
+
+mod common;
+
+use animenexa_program::{
+    error::AnimeNexaError,
+    instruction::UserInstruction,
+    pda::{
+        find_associated_token_address, find_config_address, find_content_address,
+        find_receipt_address,
+    },
+    state::{
+        AccessGrant, AccessTerms, ContributorRole, MonetizedContent, Payout, PlatformConfig,
+        Purchase, RevenueShare,
+    },
+};
+use common::{
+    mint_account, run, set_clock, system_program_account, token_account, token_program_account,
+    user_account, Fixture, TestAccount,
+};
+use solana_program::{program_error::ProgramError, program_pack::Pack, pubkey::Pubkey};
+
+const PRICE: u64 = 1_000;
+
+struct Sale {
+    f: Fixture,
+    bob_wallet: TestAccount,
+    bob_user: TestAccount,
+    content: TestAccount,
+    config: TestAccount,
+    treasury: TestAccount,
+}
+
+impl Sale {
+    // Bob's content, sold for lamports on `access_terms` with no platform fee
+    fn new(access_terms: AccessTerms) -> Self {
+        let f = Fixture::new();
+        let program_id = f.program_id;
+        let bob_wallet = TestAccount::wallet(false);
+        let bob_user = user_account(&program_id, "bob", &bob_wallet.key);
+
+        let content = MonetizedContent {
+            content_id: "content-1".to_string(),
+            creator_user_id: "bob".to_string(),
+            price: PRICE,
+            payment_mint: None,
+            revenue_shares: vec![],
+            access_terms,
+            total_sales: 0,
+            total_revenue: 0,
+        };
+        let content_address = find_content_address(&program_id, &bob_user.key, "content-1").0;
+
+        let treasury = TestAccount::wallet(false);
+        let config = PlatformConfig {
+            admin: Pubkey::new_unique(),
+            treasury: treasury.key,
+            fee_basis_points: 0,
+        };
+
+        Self {
+            content: TestAccount::state(&program_id, content_address, &content),
+            config: TestAccount::state(&program_id, find_config_address(&program_id).0, &config),
+            f,
+            bob_wallet,
+            bob_user,
+            treasury,
+        }
+    }
+
+    // The address of Alice's receipt, before she bought anything
+    fn receipt(&self) -> TestAccount {
+        let address = find_receipt_address(
+            &self.f.program_id,
+            &self.content.key,
+            &self.f.alice_user.key,
+        )
+        .0;
+        TestAccount::empty(address)
+    }
+
+    // Charge `fee_basis_points` of every sale for the platform
+    fn set_fee(&mut self, fee_basis_points: u16) {
+        let mut config: PlatformConfig = self.config.read();
+        config.fee_basis_points = fee_basis_points;
+        self.config.data = borsh::to_vec(&config).unwrap();
+    }
+
+    // Price the content in the SPL token `mint` instead of lamports
+    fn price_in(&mut self, mint: Pubkey) {
+        let mut content: MonetizedContent = self.content.read();
+        content.payment_mint = Some(mint);
+        self.content.data = borsh::to_vec(&content).unwrap();
+    }
+
+    // The token accounts PurchaseContent takes for token-priced content: Alice's
+    // and Bob's associated token accounts for `mint`, the mint and the token
+    // program. Alice holds enough for ten purchases.
+    fn token_accounts(&self, mint: Pubkey) -> [TestAccount; 4] {
+        [
+            ata(&self.f.alice_wallet.key, &mint, 10 * PRICE),
+            ata(&self.bob_wallet.key, &mint, 0),
+            mint_account(mint, 6),
+            token_program_account(),
+        ]
+    }
+
+    // Run SetRevenueShares for Bob
+    fn set_revenue_shares(
+        &mut self,
+        revenue_shares: Vec<RevenueShare>,
+    ) -> Result<(), ProgramError> {
+        let program_id = self.f.program_id;
+        let mut system_program = system_program_account();
+        let instruction = UserInstruction::SetRevenueShares {
+            content_id: "content-1".to_string(),
+            revenue_shares,
+        };
+        self.bob_wallet.is_signer = true;
+        let result = run(
+            &program_id,
+            &instruction,
+            &mut [
+                &mut self.bob_user,
+                &mut self.bob_wallet,
+                &mut self.content,
+                &mut system_program,
+            ],
+        );
+        self.bob_wallet.is_signer = false;
+        result
+    }
+
+    // Run PurchaseContent for Alice, followed by `extra` token and recipient accounts
+    fn purchase(
+        &mut self,
+        receipt: &mut TestAccount,
+        extra: &mut [TestAccount],
+    ) -> Result<(), ProgramError> {
+        let program_id = self.f.program_id;
+        let mut system_program = system_program_account();
+        let instruction = UserInstruction::PurchaseContent {
+            content_id: "content-1".to_string(),
+            purchaser_user_id: "alice".to_string(),
+            amount: PRICE,
+            timestamp: 0,
+        };
+        let mut accounts = vec![
+            &mut self.bob_wallet,
+            &mut self.f.alice_wallet,
+            &mut self.content,
+            &mut self.f.alice_user,
+            &mut self.bob_user,
+            &mut system_program,
+            &mut self.config,
+            &mut self.treasury,
+            receipt,
+        ];
+        accounts.extend(extra.iter_mut());
+        run(&program_id, &instruction, &mut accounts)
+    }
+}
+
+#[test]
+fn purchase_creates_receipt_and_counts_the_sale() {
+    let mut s = Sale::new(AccessTerms::Permanent);
+    let mut receipt = s.receipt();
+    let alice_lamports = s.f.alice_wallet.lamports;
+    let bob_lamports = s.bob_wallet.lamports;
+
+    s.purchase(&mut receipt, &mut []).unwrap();
+
+    let purchase: Purchase = receipt.read();
+    assert_eq!(receipt.owner, s.f.program_id);
+    assert_eq!(purchase.content, s.content.key);
+    assert_eq!(purchase.purchaser, s.f.alice_wallet.key);
+    assert_eq!(purchase.amount, PRICE);
+    assert_eq!(purchase.grant, AccessGrant::Permanent);
+    assert_eq!(purchase.platform_fee, 0);
+    assert_eq!(
+        purchase.payouts,
+        vec![Payout {
+            wallet: s.bob_wallet.key,
+            amount: PRICE,
+        }]
+    );
+
+    let content: MonetizedContent = s.content.read();
+    assert_eq!(content.total_sales, 1);
+    assert_eq!(content.total_revenue, PRICE);
+
+    assert_eq!(s.bob_wallet.lamports, bob_lamports + PRICE);
+    assert_eq!(
+        s.f.alice_wallet.lamports,
+        alice_lamports - PRICE - receipt.lamports
+    );
+}
+
+#[test]
+fn second_purchase_is_rejected() {
+    let mut s = Sale::new(AccessTerms::Permanent);
+    let mut receipt = s.receipt();
+    s.purchase(&mut receipt, &mut []).unwrap();
+
+    assert_eq!(
+        s.purchase(&mut receipt, &mut []),
+        Err(AnimeNexaError::AlreadyPurchased.into())
+    );
+    assert_eq!(s.content.read::<MonetizedContent>().total_sales, 1);
+}
+
+#[test]
+fn expired_rental_renews_the_receipt() {
+    let mut s = Sale::new(AccessTerms::Rental {
+        duration_seconds: 100,
+    });
+    let mut receipt = s.receipt();
+
+    set_clock(0);
+    s.purchase(&mut receipt, &mut []).unwrap();
+    assert_eq!(
+        receipt.read::<Purchase>().grant,
+        AccessGrant::Rental { expires_at: 100 }
+    );
+
+    set_clock(99);
+    assert_eq!(
+        s.purchase(&mut receipt, &mut []),
+        Err(AnimeNexaError::AlreadyPurchased.into())
+    );
+
+    // The receipt is rewritten in place, so its rent is not paid again
+    set_clock(100);
+    let alice_lamports = s.f.alice_wallet.lamports;
+    s.purchase(&mut receipt, &mut []).unwrap();
+    assert_eq!(
+        receipt.read::<Purchase>().grant,
+        AccessGrant::Rental { expires_at: 200 }
+    );
+    assert_eq!(s.f.alice_wallet.lamports, alice_lamports - PRICE);
+
+    let content: MonetizedContent = s.content.read();
+    assert_eq!(content.total_sales, 2);
+    assert_eq!(content.total_revenue, 2 * PRICE);
+}
+
+// The associated token account of `wallet` for `mint`, holding `amount`
+fn ata(wallet: &Pubkey, mint: &Pubkey, amount: u64) -> TestAccount {
+    let key = find_associated_token_address(wallet, mint).0;
+    token_account(key, mint, wallet, amount)
+}
+
+fn token_balance(account: &TestAccount) -> u64 {
+    spl_token::state::Account::unpack(&account.data)
+        .unwrap()
+        .amount
+}
+
+fn share(wallet: &TestAccount, role: ContributorRole, basis_points: u16) -> RevenueShare {
+    RevenueShare {
+        wallet: wallet.key,
+        role,
+        basis_points,
+    }
+}
+
+#[test]
+fn set_revenue_shares_rejects_shares_not_adding_up_to_the_whole() {
+    let mut s = Sale::new(AccessTerms::Permanent);
+    let artist = TestAccount::wallet(false);
+    let writer = TestAccount::wallet(false);
+
+    for basis_points in [(6_000, 3_999), (6_000, 4_001)] {
+        let shares = vec![
+            share(&artist, ContributorRole::Artist, basis_points.0),
+            share(&writer, ContributorRole::Writer, basis_points.1),
+        ];
+        assert_eq!(
+            s.set_revenue_shares(shares),
+            Err(AnimeNexaError::InvalidRevenueShares.into())
+        );
+    }
+    assert!(s
+        .content
+        .read::<MonetizedContent>()
+        .revenue_shares
+        .is_empty());
+}
+
+#[test]
+fn purchase_pays_the_fee_and_every_share() {
+    let mut s = Sale::new(AccessTerms::Permanent);
+    // The artist's and the writer's wallets, in table order
+    let mut recipients = [TestAccount::wallet(false), TestAccount::wallet(false)];
+    s.set_fee(250);
+    s.set_revenue_shares(vec![
+        share(&recipients[0], ContributorRole::Artist, 6_000),
+        share(&recipients[1], ContributorRole::Writer, 4_000),
+    ])
+    .unwrap();
+
+    let treasury_lamports = s.treasury.lamports;
+    let recipient_lamports = recipients.each_ref().map(|recipient| recipient.lamports);
+    let bob_lamports = s.bob_wallet.lamports;
+    let mut receipt = s.receipt();
+    s.purchase(&mut receipt, &mut recipients).unwrap();
+
+    // 2.5% of 1000 goes to the treasury and the remaining 975 is split 60/40,
+    // which leaves nothing to the creator
+    assert_eq!(s.treasury.lamports, treasury_lamports + 25);
+    assert_eq!(recipients[0].lamports, recipient_lamports[0] + 585);
+    assert_eq!(recipients[1].lamports, recipient_lamports[1] + 390);
+    assert_eq!(s.bob_wallet.lamports, bob_lamports);
+
+    let purchase: Purchase = receipt.read();
+    assert_eq!(purchase.platform_fee, 25);
+    assert_eq!(
+        purchase.payouts,
+        vec![
+            Payout {
+                wallet: recipients[0].key,
+                amount: 585,
+            },
+            Payout {
+                wallet: recipients[1].key,
+                amount: 390,
+            },
+            Payout {
+                wallet: s.bob_wallet.key,
+                amount: 0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn token_purchase_pays_the_fee_shares_and_creator_in_tokens() {
+    let mut s = Sale::new(AccessTerms::Permanent);
+    let mint = Pubkey::new_unique();
+    let artist = TestAccount::wallet(false);
+    let writer = TestAccount::wallet(false);
+    s.price_in(mint);
+    s.set_fee(250);
+    s.set_revenue_shares(vec![
+        share(&artist, ContributorRole::Artist, 5_000),
+        share(&writer, ContributorRole::Writer, 5_000),
+    ])
+    .unwrap();
+
+    // The treasury and the contributors are paid into their token accounts
+    s.treasury = ata(&s.treasury.key, &mint, 0);
+    let mut accounts: Vec<TestAccount> = s.token_accounts(mint).into();
+    accounts.push(ata(&artist.key, &mint, 0));
+    accounts.push(ata(&writer.key, &mint, 0));
+    let bob_lamports = s.bob_wallet.lamports;
+    let mut receipt = s.receipt();
+    s.purchase(&mut receipt, &mut accounts).unwrap();
+
+    // 2.5% of 1000 goes to the treasury and the remaining 975 is split in half,
+    // leaving the rounding dust to the creator
+    assert_eq!(token_balance(&accounts[0]), 9 * PRICE);
+    assert_eq!(token_balance(&s.treasury), 25);
+    assert_eq!(token_balance(&accounts[4]), 487);
+    assert_eq!(token_balance(&accounts[5]), 487);
+    assert_eq!(token_balance(&accounts[1]), 1);
+    assert_eq!(s.bob_wallet.lamports, bob_lamports);
+
+    let purchase: Purchase = receipt.read();
+    assert_eq!(purchase.payment_mint, Some(mint));
+    assert_eq!(purchase.platform_fee, 25);
+    assert_eq!(
+        purchase.payouts,
+        vec![
+            Payout {
+                wallet: artist.key,
+                amount: 487,
+            },
+            Payout {
+                wallet: writer.key,
+                amount: 487,
+            },
+            Payout {
+                wallet: s.bob_wallet.key,
+                amount: 1,
+            },
+        ]
+    );
+}
+
+#[test]
+fn token_purchase_rejects_another_mint() {
+    let mut s = Sale::new(AccessTerms::Permanent);
+    let mint = Pubkey::new_unique();
+    s.price_in(mint);
+    let mut receipt = s.receipt();
+
+    let mut accounts = s.token_accounts(Pubkey::new_unique());
+    assert_eq!(
+        s.purchase(&mut receipt, &mut accounts),
+        Err(AnimeNexaError::PaymentMintMismatch.into())
+    );
+}
+
+#[test]
+fn token_purchase_rejects_source_other_than_the_buyers_token_account() {
+    let mut s = Sale::new(AccessTerms::Permanent);
+    let mint = Pubkey::new_unique();
+    s.price_in(mint);
+    let mut receipt = s.receipt();
+
+    let mut accounts = s.token_accounts(mint);
+    accounts[0].key = Pubkey::new_unique();
+    assert_eq!(
+        s.purchase(&mut receipt, &mut accounts),
+        Err(AnimeNexaError::TokenAccountMismatch.into())
+    );
+}
+
+#[test]
+fn token_purchase_rejects_another_token_program() {
+    let mut s = Sale::new(AccessTerms::Permanent);
+    let mint = Pubkey::new_unique();
+    s.price_in(mint);
+    let mut receipt = s.receipt();
+
+    let mut accounts = s.token_accounts(mint);
+    accounts[3].key = Pubkey::new_unique();
+    assert_eq!(
+        s.purchase(&mut receipt, &mut accounts),
+        Err(ProgramError::IncorrectProgramId)
+    );
+}
+
+#[test]
+fn monetize_content_rejects_another_content_id() {
+    let mut s = Sale::new(AccessTerms::Permanent);
+    let program_id = s.f.program_id;
+    let mut system_program = system_program_account();
+    s.bob_wallet.is_signer = true;
+
+    // The account holds "content-1", but the instruction names "content-2"
+    let instruction = UserInstruction::MonetizeContent {
+        content_id: "content-2".to_string(),
+        creator_user_id: "bob".to_string(),
+        price: 1,
+        payment_mint: None,
+        access_terms: AccessTerms::Permanent,
+    };
+    let result = run(
+        &program_id,
+        &instruction,
+        &mut [
+            &mut s.bob_user,
+            &mut s.bob_wallet,
+            &mut s.content,
+            &mut system_program,
+        ],
+    );
+    assert_eq!(result, Err(AnimeNexaError::ContentIdMismatch.into()));
+}
@@ -0,0 +1,204 @@
+//! Reacting to posts and comments, switching reactions and taking them back.
+//!
+//! Alice reacts to Bob's post "post-1".
+
+mod common;
+
+use animenexa_program::{
+    error::AnimeNexaError,
+    instruction::UserInstruction,
+    pda::find_reaction_address,
+    state::{Comment, Post, Reaction, ReactionCounts, ReactionKind, ReactionTarget},
+};
+use common::{
+    comment, comment_account, post, post_account, run, system_program_account, user_account,
+    Fixture, TestAccount,
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+struct Target {
+    f: Fixture,
+    bob_user: TestAccount,
+    target: TestAccount,
+    reaction: TestAccount,
+}
+
+impl Target {
+    fn post() -> Self {
+        let f = Fixture::new();
+        let bob_user = user_account(&f.program_id, "bob", &Pubkey::new_unique());
+        let target = post_account(&f.program_id, &bob_user, &post("post-1", "bob", "hello"));
+        Self::new(f, bob_user, target)
+    }
+
+    fn comment() -> Self {
+        let f = Fixture::new();
+        let bob_user = user_account(&f.program_id, "bob", &Pubkey::new_unique());
+        let comment = comment("comment-1", &bob_user, Pubkey::new_unique(), None);
+        let target = comment_account(&f.program_id, &comment);
+        Self::new(f, bob_user, target)
+    }
+
+    fn new(f: Fixture, bob_user: TestAccount, target: TestAccount) -> Self {
+        let reaction_address =
+            find_reaction_address(&f.program_id, &target.key, &f.alice_user.key).0;
+        Self {
+            reaction: TestAccount::empty(reaction_address),
+            f,
+            bob_user,
+            target,
+        }
+    }
+
+    // Mark the target as deleted
+    fn delete<T: borsh::BorshSerialize + borsh::BorshDeserialize>(&mut self, mark: fn(&mut T)) {
+        let mut target: T = self.target.read();
+        mark(&mut target);
+        self.target.data = borsh::to_vec(&target).unwrap();
+    }
+
+    fn react(&mut self, target: ReactionTarget, kind: ReactionKind) -> Result<(), ProgramError> {
+        let program_id = self.f.program_id;
+        let mut system_program = system_program_account();
+        let instruction = UserInstruction::React {
+            user_id: "alice".to_string(),
+            target,
+            kind,
+        };
+        run(
+            &program_id,
+            &instruction,
+            &mut [
+                &mut self.f.alice_user,
+                &mut self.f.alice_wallet,
+                &mut self.target,
+                &mut self.bob_user,
+                &mut self.reaction,
+                &mut system_program,
+            ],
+        )
+    }
+
+    fn unreact(&mut self) -> Result<(), ProgramError> {
+        let program_id = self.f.program_id;
+        run(
+            &program_id,
+            &UserInstruction::Unreact,
+            &mut [
+                &mut self.f.alice_user,
+                &mut self.f.alice_wallet,
+                &mut self.target,
+                &mut self.bob_user,
+                &mut self.reaction,
+            ],
+        )
+    }
+
+    fn post_counts(&self) -> ReactionCounts {
+        self.target.read::<Post>().reactions
+    }
+}
+
+#[test]
+fn react_counts_the_reaction_on_the_post() {
+    let mut t = Target::post();
+
+    t.react(ReactionTarget::Post, ReactionKind::Like).unwrap();
+
+    assert_eq!(t.post_counts().get(ReactionKind::Like), 1);
+    let reaction: Reaction = t.reaction.read();
+    assert_eq!(reaction.user, t.f.alice_user.key);
+    assert_eq!(reaction.target, t.target.key);
+    assert_eq!(reaction.kind, ReactionKind::Like);
+}
+
+#[test]
+fn reacting_twice_with_the_same_kind_counts_once() {
+    let mut t = Target::post();
+
+    t.react(ReactionTarget::Post, ReactionKind::Like).unwrap();
+    t.react(ReactionTarget::Post, ReactionKind::Like).unwrap();
+
+    assert_eq!(t.post_counts().get(ReactionKind::Like), 1);
+}
+
+#[test]
+fn reacting_with_another_kind_moves_the_count() {
+    let mut t = Target::post();
+
+    t.react(ReactionTarget::Post, ReactionKind::Like).unwrap();
+    t.react(ReactionTarget::Post, ReactionKind::Fire).unwrap();
+
+    let counts = t.post_counts();
+    assert_eq!(counts.get(ReactionKind::Like), 0);
+    assert_eq!(counts.get(ReactionKind::Fire), 1);
+    assert_eq!(t.reaction.read::<Reaction>().kind, ReactionKind::Fire);
+}
+
+#[test]
+fn reacting_again_as_another_target_kind_is_rejected() {
+    let mut t = Target::post();
+
+    t.react(ReactionTarget::Post, ReactionKind::Like).unwrap();
+    assert_eq!(
+        t.react(ReactionTarget::Comment, ReactionKind::Love),
+        Err(AnimeNexaError::ReactionMismatch.into())
+    );
+}
+
+#[test]
+fn unreact_uncounts_the_reaction_and_refunds_its_rent() {
+    let mut t = Target::post();
+    let wallet_lamports = t.f.alice_wallet.lamports;
+
+    t.react(ReactionTarget::Post, ReactionKind::Love).unwrap();
+    assert!(t.f.alice_wallet.lamports < wallet_lamports);
+    t.unreact().unwrap();
+
+    assert_eq!(t.post_counts().get(ReactionKind::Love), 0);
+    assert_eq!(t.reaction.lamports, 0);
+    assert!(t.reaction.data.is_empty());
+    assert_eq!(t.f.alice_wallet.lamports, wallet_lamports);
+}
+
+#[test]
+fn unreact_without_a_reaction_is_rejected() {
+    let mut t = Target::post();
+
+    assert_eq!(t.unreact(), Err(ProgramError::UninitializedAccount));
+}
+
+#[test]
+fn reacting_to_a_deleted_post_is_rejected_but_unreacting_is_not() {
+    let mut t = Target::post();
+    t.react(ReactionTarget::Post, ReactionKind::Like).unwrap();
+    t.delete(|post: &mut Post| post.deleted = true);
+
+    assert_eq!(
+        t.react(ReactionTarget::Post, ReactionKind::Sad),
+        Err(AnimeNexaError::PostDeleted.into())
+    );
+    t.unreact().unwrap();
+    assert_eq!(t.post_counts().get(ReactionKind::Like), 0);
+}
+
+#[test]
+fn reacting_to_a_deleted_comment_is_rejected() {
+    let mut t = Target::comment();
+    t.delete(|comment: &mut Comment| comment.deleted = true);
+
+    assert_eq!(
+        t.react(ReactionTarget::Comment, ReactionKind::Like),
+        Err(AnimeNexaError::CommentDeleted.into())
+    );
+}
+
+#[test]
+fn react_counts_the_reaction_on_a_comment() {
+    let mut t = Target::comment();
+
+    t.react(ReactionTarget::Comment, ReactionKind::Wow).unwrap();
+
+    let comment: Comment = t.target.read();
+    assert_eq!(comment.reactions.get(ReactionKind::Wow), 1);
+}
@@ -0,0 +1,183 @@
+//! Tipping users: paying the recipient's wallet, recording the tip and
+//! counting it on the recipient.
+//!
+//! Alice tips Bob.
+
+mod common;
+
+use animenexa_program::{
+    error::AnimeNexaError,
+    instruction::UserInstruction,
+    pda::find_support_address,
+    state::{support::MAX_SUPPORT_MESSAGE_LEN, Support, User},
+};
+use common::{run, system_program_account, user_account, Fixture, TestAccount};
+use solana_program::program_error::ProgramError;
+
+struct Tip {
+    f: Fixture,
+    bob_user: TestAccount,
+    bob_wallet: TestAccount,
+    support_id: &'static str,
+    support: TestAccount,
+}
+
+impl Tip {
+    // Alice and Bob, before Alice's tip "support-1" was created
+    fn new() -> Self {
+        let f = Fixture::new();
+        let bob_wallet = TestAccount::wallet(false);
+        let bob_user = user_account(&f.program_id, "bob", &bob_wallet.key);
+        let mut tip = Self {
+            f,
+            bob_user,
+            bob_wallet,
+            support_id: "",
+            support: TestAccount::wallet(false),
+        };
+        tip.next("support-1");
+        tip
+    }
+
+    // Send the following tips under `support_id`
+    fn next(&mut self, support_id: &'static str) {
+        let address =
+            find_support_address(&self.f.program_id, &self.f.alice_user.key, support_id).0;
+        self.support_id = support_id;
+        self.support = TestAccount::empty(address);
+    }
+
+    // Run SupportUser from Alice to `recipient`
+    fn send(
+        &mut self,
+        amount: u64,
+        message: Option<String>,
+        recipient: Recipient,
+    ) -> Result<(), ProgramError> {
+        let program_id = self.f.program_id;
+        let mut other_wallet = TestAccount::wallet(false);
+        let mut alice_again = user_account(&program_id, "alice", &self.f.alice_wallet.key);
+        let mut alice_wallet_again = TestAccount {
+            key: self.f.alice_wallet.key,
+            ..TestAccount::wallet(false)
+        };
+        let (recipient_user_id, recipient_user, recipient_wallet) = match recipient {
+            Recipient::Bob => ("bob", &mut self.bob_user, &mut self.bob_wallet),
+            Recipient::BobAtAnotherWallet => ("bob", &mut self.bob_user, &mut other_wallet),
+            Recipient::Alice => ("alice", &mut alice_again, &mut alice_wallet_again),
+        };
+        let instruction = UserInstruction::SupportUser {
+            support_id: self.support_id.to_string(),
+            sender_user_id: "alice".to_string(),
+            recipient_user_id: recipient_user_id.to_string(),
+            amount,
+            timestamp: 42,
+            message,
+        };
+        let mut system_program = system_program_account();
+        run(
+            &program_id,
+            &instruction,
+            &mut [
+                &mut self.f.alice_user,
+                &mut self.f.alice_wallet,
+                recipient_user,
+                recipient_wallet,
+                &mut self.support,
+                &mut system_program,
+            ],
+        )
+    }
+}
+
+enum Recipient {
+    Bob,
+    // Bob's user account, with a wallet other than his as the payee
+    BobAtAnotherWallet,
+    Alice,
+}
+
+#[test]
+fn support_user_pays_the_recipient_and_records_the_tip() {
+    let mut t = Tip::new();
+    let alice_lamports = t.f.alice_wallet.lamports;
+    let bob_lamports = t.bob_wallet.lamports;
+
+    t.send(500, Some("thanks!".to_string()), Recipient::Bob)
+        .unwrap();
+
+    assert_eq!(t.bob_wallet.lamports, bob_lamports + 500);
+    assert_eq!(
+        t.f.alice_wallet.lamports,
+        alice_lamports - 500 - t.support.lamports
+    );
+    assert_eq!(t.support.owner, t.f.program_id);
+    assert_eq!(
+        t.support.read::<Support>(),
+        Support {
+            support_id: "support-1".to_string(),
+            sender_user_id: "alice".to_string(),
+            sender: t.f.alice_user.key,
+            recipient_user_id: "bob".to_string(),
+            recipient: t.bob_user.key,
+            amount: 500,
+            message: Some("thanks!".to_string()),
+            timestamp: 42,
+        }
+    );
+}
+
+#[test]
+fn support_user_adds_to_the_recipient_totals() {
+    let mut t = Tip::new();
+    t.send(500, None, Recipient::Bob).unwrap();
+
+    t.next("support-2");
+    t.send(250, None, Recipient::Bob).unwrap();
+
+    let bob: User = t.bob_user.read();
+    assert_eq!(bob.tips_received_total, 750);
+    assert_eq!(bob.tips_received_count, 2);
+}
+
+#[test]
+fn support_user_rejects_a_zero_amount() {
+    let mut t = Tip::new();
+    assert_eq!(
+        t.send(0, None, Recipient::Bob),
+        Err(AnimeNexaError::InvalidAmount.into())
+    );
+}
+
+#[test]
+fn support_user_rejects_a_message_too_long() {
+    let mut t = Tip::new();
+    let message = "a".repeat(MAX_SUPPORT_MESSAGE_LEN + 1);
+    assert_eq!(
+        t.send(500, Some(message), Recipient::Bob),
+        Err(AnimeNexaError::TextTooLong.into())
+    );
+
+    let message = "a".repeat(MAX_SUPPORT_MESSAGE_LEN);
+    t.send(500, Some(message), Recipient::Bob).unwrap();
+}
+
+#[test]
+fn support_user_rejects_a_wallet_other_than_the_recipient() {
+    let mut t = Tip::new();
+    assert_eq!(
+        t.send(500, None, Recipient::BobAtAnotherWallet),
+        Err(AnimeNexaError::WalletMismatch.into())
+    );
+    assert_eq!(t.bob_user.read::<User>().tips_received_count, 0);
+}
+
+#[test]
+fn support_user_rejects_self_tip() {
+    let mut t = Tip::new();
+    // Alice tips herself to bump her lifetime totals for only the fee
+    assert_eq!(
+        t.send(500, None, Recipient::Alice),
+        Err(AnimeNexaError::CannotTipSelf.into())
+    );
+}
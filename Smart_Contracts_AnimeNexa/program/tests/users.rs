@@ -0,0 +1,180 @@
+//! Registering users under globally unique usernames.
+
+mod common;
+
+use animenexa_program::{
+    error::AnimeNexaError,
+    instruction::UserInstruction,
+    pda::{find_user_address, find_username_address},
+    state::{User, UsernameRecord},
+};
+use common::{run, system_program_account, TestAccount};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+// The user PDA of `wallet` and the registry PDA of `username`, as passed to RegisterUser
+fn accounts(program_id: &Pubkey, wallet: &TestAccount, username: &str) -> [TestAccount; 2] {
+    [
+        TestAccount::empty(find_user_address(program_id, &wallet.key).0),
+        TestAccount::empty(find_username_address(program_id, username).0),
+    ]
+}
+
+// Run RegisterUser for `wallet`
+fn register(
+    program_id: &Pubkey,
+    wallet: &mut TestAccount,
+    username: &str,
+    user: &mut TestAccount,
+    registry: &mut TestAccount,
+) -> Result<(), ProgramError> {
+    let mut system_program = system_program_account();
+    let instruction = UserInstruction::RegisterUser {
+        user_id: username.trim().to_string(),
+        username: username.to_string(),
+        profile_data: String::new(),
+    };
+    run(
+        program_id,
+        &instruction,
+        &mut [wallet, user, registry, &mut system_program],
+    )
+}
+
+#[test]
+fn register_user_creates_user_and_registry_entry() {
+    let program_id = Pubkey::new_unique();
+    let mut wallet = TestAccount::wallet(true);
+    let [mut user, mut registry] = accounts(&program_id, &wallet, "Alice");
+
+    register(&program_id, &mut wallet, "Alice", &mut user, &mut registry).unwrap();
+
+    assert_eq!(user.owner, program_id);
+    let registered: User = user.read();
+    assert_eq!(registered.username, "Alice");
+    assert_eq!(registered.public_key, wallet.key);
+
+    assert_eq!(registry.owner, program_id);
+    assert_eq!(
+        registry.read::<UsernameRecord>(),
+        UsernameRecord {
+            username: "alice".to_string(),
+            owner: wallet.key,
+            user_account: user.key,
+        }
+    );
+}
+
+#[test]
+fn register_user_stores_the_username_without_surrounding_whitespace() {
+    let program_id = Pubkey::new_unique();
+    let mut wallet = TestAccount::wallet(true);
+    let [mut user, mut registry] = accounts(&program_id, &wallet, "  Alice ");
+
+    register(
+        &program_id,
+        &mut wallet,
+        "  Alice ",
+        &mut user,
+        &mut registry,
+    )
+    .unwrap();
+
+    assert_eq!(user.read::<User>().username, "Alice");
+    assert_eq!(registry.read::<UsernameRecord>().username, "alice");
+}
+
+#[test]
+fn register_user_rejects_a_taken_username() {
+    let program_id = Pubkey::new_unique();
+    let mut alice_wallet = TestAccount::wallet(true);
+    let [mut alice_user, mut registry] = accounts(&program_id, &alice_wallet, "alice");
+    register(
+        &program_id,
+        &mut alice_wallet,
+        "alice",
+        &mut alice_user,
+        &mut registry,
+    )
+    .unwrap();
+
+    let mut mallory_wallet = TestAccount::wallet(true);
+    let [mut mallory_user, _] = accounts(&program_id, &mallory_wallet, "alice");
+    assert_eq!(
+        register(
+            &program_id,
+            &mut mallory_wallet,
+            "alice",
+            &mut mallory_user,
+            &mut registry,
+        ),
+        Err(AnimeNexaError::UsernameTaken.into())
+    );
+}
+
+#[test]
+fn register_user_rejects_a_case_or_whitespace_variant() {
+    let program_id = Pubkey::new_unique();
+    let mut alice_wallet = TestAccount::wallet(true);
+    let [mut alice_user, mut registry] = accounts(&program_id, &alice_wallet, "alice");
+    register(
+        &program_id,
+        &mut alice_wallet,
+        "alice",
+        &mut alice_user,
+        &mut registry,
+    )
+    .unwrap();
+
+    // " Alice" normalizes to "alice", so it derives the same registry entry
+    let mut mallory_wallet = TestAccount::wallet(true);
+    let [mut mallory_user, variant_registry] = accounts(&program_id, &mallory_wallet, " Alice");
+    assert_eq!(variant_registry.key, registry.key);
+    assert_eq!(
+        register(
+            &program_id,
+            &mut mallory_wallet,
+            " Alice",
+            &mut mallory_user,
+            &mut registry,
+        ),
+        Err(AnimeNexaError::UsernameTaken.into())
+    );
+}
+
+#[test]
+fn register_user_rejects_a_second_registration_from_the_same_wallet() {
+    let program_id = Pubkey::new_unique();
+    let mut wallet = TestAccount::wallet(true);
+    let [mut user, mut registry] = accounts(&program_id, &wallet, "alice");
+    register(&program_id, &mut wallet, "alice", &mut user, &mut registry).unwrap();
+
+    let [_, mut other_registry] = accounts(&program_id, &wallet, "alice_2");
+    assert_eq!(
+        register(
+            &program_id,
+            &mut wallet,
+            "alice_2",
+            &mut user,
+            &mut other_registry,
+        ),
+        Err(AnimeNexaError::UserAlreadyRegistered.into())
+    );
+}
+
+#[test]
+fn register_user_rejects_invalid_usernames() {
+    let program_id = Pubkey::new_unique();
+    let mut wallet = TestAccount::wallet(true);
+
+    // The name is checked before any address, and one too long cannot be a seed
+    for username in ["", "   ", "alice!", "al ice", &"a".repeat(33)] {
+        let mut user = TestAccount::empty(find_user_address(&program_id, &wallet.key).0);
+        let mut registry = TestAccount::empty(Pubkey::new_unique());
+        assert_eq!(
+            register(&program_id, &mut wallet, username, &mut user, &mut registry),
+            Err(AnimeNexaError::InvalidUsername.into()),
+            "{:?}",
+            username
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::reaction::ReactionCounts;
+
+/// Longest comment text accepted.
+pub const MAX_COMMENT_LEN: usize = 1000;
+
+// Define the Comment struct
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Comment {
+    pub comment_id: String,
+    pub user_id: String,
+    pub author: Pubkey,                 // Commenter's user account
+    pub post: Pubkey,                   // Post the thread belongs to
+    pub parent_comment: Option<Pubkey>, // Comment replied to, or None for a reply to the post
+    pub text: String,
+    pub created_at: i64, // Unix timestamp from the Clock sysvar
+    pub reply_count: u32,
+    pub deleted: bool, // Tombstone: the text has been removed
+    pub reactions: ReactionCounts,
+}
@@ -0,0 +1,13 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+/// Denominator for every basis-point value (fees and revenue shares).
+pub const BASIS_POINTS_DENOMINATOR: u16 = 10_000;
+
+// Global platform settings, stored in a single config PDA
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct PlatformConfig {
+    pub admin: Pubkey,         // Wallet allowed to update the config
+    pub treasury: Pubkey,      // Wallet receiving platform fees
+    pub fee_basis_points: u16, // Platform cut of every purchase
+}
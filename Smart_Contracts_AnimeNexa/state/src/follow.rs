@@ -0,0 +1,10 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Define the Follow struct: an edge of the follow graph
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Follow {
+    pub follower: Pubkey, // Follower's user account
+    pub followee: Pubkey, // Followed user's account
+    pub followed_at: i64, // Unix timestamp from the Clock sysvar
+}
@@ -0,0 +1,69 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+/// Most members a single group account may hold.
+pub const MAX_GROUP_MEMBERS: usize = 100;
+
+// Define the Group struct
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Group {
+    pub group_id: String,
+    pub creator: Pubkey, // Creator's user account, part of the group's address
+    pub owner: Pubkey,   // Current owner's user account
+    pub members: Vec<GroupMember>, // The owner included
+}
+
+// Define the Group Member struct
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct GroupMember {
+    pub user_id: String,
+    pub user: Pubkey, // Member's user account
+    pub role: GroupRole,
+}
+
+/// What a member may do in a group, from most to least privileged.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupRole {
+    Owner,
+    Admin,
+    Moderator,
+    Member,
+}
+
+impl GroupRole {
+    fn rank(self) -> u8 {
+        match self {
+            GroupRole::Owner => 3,
+            GroupRole::Admin => 2,
+            GroupRole::Moderator => 1,
+            GroupRole::Member => 0,
+        }
+    }
+
+    /// Whether this role is strictly more privileged than `other`.
+    pub fn outranks(self, other: GroupRole) -> bool {
+        self.rank() > other.rank()
+    }
+}
+
+impl Group {
+    /// The membership of `user`, if it belongs to the group.
+    pub fn member(&self, user: &Pubkey) -> Option<&GroupMember> {
+        self.members.iter().find(|member| member.user == *user)
+    }
+
+    /// Mutable membership of `user`, if it belongs to the group.
+    pub fn member_mut(&mut self, user: &Pubkey) -> Option<&mut GroupMember> {
+        self.members.iter_mut().find(|member| member.user == *user)
+    }
+}
+
+// Define the Group Message struct
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct GroupMessage {
+    pub message_id: String,
+    pub group_id: String,
+    pub user_id: String,
+    pub message_content: String, // Ideally, this should be encrypted
+    pub timestamp: u64,
+}
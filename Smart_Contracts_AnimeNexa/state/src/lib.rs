@@ -0,0 +1,32 @@
+//! Account state shared by the AnimeNexa programs.
+//!
+//! Every account owned by an AnimeNexa program stores one of these structs,
+//! borsh-encoded, so clients and programs agree on a single layout.
+
+pub mod comment;
+pub mod config;
+pub mod follow;
+pub mod group;
+pub mod manga;
+pub mod media;
+pub mod message;
+pub mod monetization;
+pub mod post;
+pub mod reaction;
+pub mod support;
+pub mod user;
+
+pub use comment::Comment;
+pub use config::PlatformConfig;
+pub use follow::Follow;
+pub use group::{Group, GroupMember, GroupMessage, GroupRole};
+pub use manga::{AccessGrant, Chapter, Manga, MangaAccess};
+pub use media::{Media, MediaLocator, MediaReference, MediaType};
+pub use message::Message;
+pub use monetization::{
+    AccessTerms, ContributorRole, MonetizedContent, Payout, Purchase, RevenueShare,
+};
+pub use post::{ContentWarning, Post, PostContent, PostVisibility, RepostRecord};
+pub use reaction::{Reaction, ReactionCounts, ReactionKind, ReactionTarget};
+pub use support::Support;
+pub use user::{User, UsernameRecord};
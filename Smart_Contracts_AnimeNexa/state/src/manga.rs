@@ -0,0 +1,95 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::hash::hashv;
+
+use crate::{media::MediaReference, reaction::ReactionCounts};
+
+/// Longest chapter title accepted.
+pub const MAX_CHAPTER_TITLE_LEN: usize = 128;
+
+/// Most pages a single chapter account may list.
+pub const MAX_CHAPTER_PAGES: usize = 100;
+
+// Define the Manga struct
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Manga {
+    pub manga_id: String,
+    pub creator_user_id: String,
+    pub title: String,
+    pub description: String,
+    pub timestamp: u64,
+    pub is_free: bool,      // Readable without a purchase
+    pub chapter_count: u32, // Chapters currently published
+    pub free_chapters: u32, // Chapters 1..=free_chapters are readable without a purchase
+    pub reactions: ReactionCounts,
+}
+
+// Define the Chapter struct
+//
+// Each chapter lives in its own account, so a series is not bounded by the
+// size of the manga account.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Chapter {
+    pub manga_id: String,
+    pub chapter_number: u32,
+    pub title: String,
+    pub pages: Vec<MediaReference>, // In reading order
+    pub content_hash: [u8; 32],     // `Chapter::hash_pages` of `pages`
+    pub price: u64, // In the series' payment currency; 0 when only sold with the series
+    pub published_at: i64, // Unix timestamp from the Clock sysvar
+    pub updated_at: i64,
+}
+
+impl Chapter {
+    /// SHA-256 over the `sha256` of each page, in reading order, so the hash
+    /// changes when a page is replaced, added, removed or moved.
+    pub fn hash_pages(pages: &[MediaReference]) -> [u8; 32] {
+        let page_hashes: Vec<&[u8]> = pages.iter().map(|page| page.sha256.as_ref()).collect();
+        hashv(&page_hashes).to_bytes()
+    }
+}
+
+// Define the Manga Access struct
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct MangaAccess {
+    pub manga_id: String,
+    pub user_id: String,
+    pub grant: AccessGrant,
+    pub granted_at: i64, // Unix timestamp from the Clock sysvar
+}
+
+/// What a user may read of a manga, and until when.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessGrant {
+    // Every chapter, forever
+    Permanent,
+    // Every chapter, until the Unix timestamp `expires_at`
+    Rental {
+        expires_at: i64,
+    },
+    // Chapters `first_chapter..=last_chapter`, forever
+    ChapterRange {
+        first_chapter: u32,
+        last_chapter: u32,
+    },
+}
+
+impl AccessGrant {
+    /// Whether the grant still gives access at Unix time `now`.
+    pub fn is_live(&self, now: i64) -> bool {
+        match self {
+            AccessGrant::Rental { expires_at } => now < *expires_at,
+            AccessGrant::Permanent | AccessGrant::ChapterRange { .. } => true,
+        }
+    }
+
+    /// Whether the grant gives access to `chapter` at Unix time `now`.
+    pub fn covers_chapter(&self, chapter: u32, now: i64) -> bool {
+        match self {
+            AccessGrant::ChapterRange {
+                first_chapter,
+                last_chapter,
+            } => (*first_chapter..=*last_chapter).contains(&chapter),
+            _ => self.is_live(now),
+        }
+    }
+}
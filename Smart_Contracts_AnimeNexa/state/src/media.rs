@@ -0,0 +1,128 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// Longest URL or content identifier accepted in a [`MediaLocator`].
+pub const MAX_LOCATOR_LEN: usize = 256;
+
+/// Longest MIME type accepted, as allowed by RFC 6838.
+pub const MAX_MIME_TYPE_LEN: usize = 127;
+
+/// Length of an Arweave transaction ID, base64url-encoded.
+pub const ARWEAVE_TX_ID_LEN: usize = 43;
+
+const MIB: u64 = 1024 * 1024;
+
+// Largest file accepted for each media type, in bytes
+pub const MAX_IMAGE_SIZE: u64 = 20 * MIB;
+pub const MAX_GIF_SIZE: u64 = 15 * MIB;
+pub const MAX_MANGA_PAGE_SIZE: u64 = 20 * MIB;
+pub const MAX_VIDEO_SIZE: u64 = 2048 * MIB;
+pub const MAX_AUDIO_SIZE: u64 = 500 * MIB;
+pub const MAX_OTHER_SIZE: u64 = 100 * MIB;
+
+/// Longest video or audio accepted, in seconds.
+pub const MAX_DURATION_SECONDS: u32 = 3 * 60 * 60;
+
+// Define the media struct
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Media {
+    pub media_id: String,
+    pub post_id: String,
+    pub user_id: String,
+    pub reference: MediaReference,
+    pub media_type: MediaType,
+}
+
+/// Kind of a media file, with the metadata renderers need for it.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MediaType {
+    Image,
+    Video { duration_seconds: u32 },
+    Audio { duration_seconds: u32 },
+    Gif,
+    MangaPage,
+    Other,
+}
+
+impl MediaType {
+    /// Whether `mime_type` is one this kind of media can be served as. MIME
+    /// types are case-insensitive, so `IMAGE/PNG` is an image like `image/png`.
+    pub fn accepts_mime_type(&self, mime_type: &str) -> bool {
+        let (kind, subtype) = mime_type.split_once('/').unwrap_or_default();
+        let is_image = kind.eq_ignore_ascii_case("image");
+        let is_gif = is_image && subtype.eq_ignore_ascii_case("gif");
+        match self {
+            MediaType::Image | MediaType::MangaPage => is_image && !is_gif,
+            MediaType::Gif => is_gif,
+            MediaType::Video { .. } => kind.eq_ignore_ascii_case("video"),
+            MediaType::Audio { .. } => kind.eq_ignore_ascii_case("audio"),
+            MediaType::Other => true,
+        }
+    }
+
+    /// Whether a file of `byte_size` bytes, and its duration if any, are
+    /// within the limits for this kind of media.
+    pub fn within_limits(&self, byte_size: u64) -> bool {
+        let (max_size, duration_seconds) = match *self {
+            MediaType::Image => (MAX_IMAGE_SIZE, None),
+            MediaType::Gif => (MAX_GIF_SIZE, None),
+            MediaType::MangaPage => (MAX_MANGA_PAGE_SIZE, None),
+            MediaType::Video { duration_seconds } => (MAX_VIDEO_SIZE, Some(duration_seconds)),
+            MediaType::Audio { duration_seconds } => (MAX_AUDIO_SIZE, Some(duration_seconds)),
+            MediaType::Other => (MAX_OTHER_SIZE, None),
+        };
+
+        let duration_valid = match duration_seconds {
+            Some(duration) => (1..=MAX_DURATION_SECONDS).contains(&duration),
+            None => true,
+        };
+
+        byte_size <= max_size && duration_valid
+    }
+}
+
+/// A media file as registered by its creator, so anyone serving or reading it
+/// can check they hold the same bytes.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct MediaReference {
+    pub locator: MediaLocator,
+    pub sha256: [u8; 32], // SHA-256 of the file's bytes
+    pub byte_size: u64,
+    pub mime_type: String,
+}
+
+/// Where a media file can be fetched from.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum MediaLocator {
+    // Location-addressed; only the hash ties it to the registered file
+    Url(String),
+    // Content-addressed on IPFS
+    Ipfs { cid: String },
+    // Content-addressed on Arweave
+    Arweave { tx_id: String },
+}
+
+impl MediaReference {
+    /// Whether the reference is well-formed: a non-empty file, a `type/subtype`
+    /// MIME type and a locator within the length limits.
+    pub fn is_valid(&self) -> bool {
+        let mime_type_valid = self.mime_type.len() <= MAX_MIME_TYPE_LEN
+            && self
+                .mime_type
+                .split_once('/')
+                .is_some_and(|(kind, subtype)| !kind.is_empty() && !subtype.is_empty());
+
+        self.byte_size > 0 && mime_type_valid && self.locator.is_valid()
+    }
+}
+
+impl MediaLocator {
+    /// Whether the locator is non-empty and within the length limits.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            MediaLocator::Url(url) | MediaLocator::Ipfs { cid: url } => {
+                !url.is_empty() && url.len() <= MAX_LOCATOR_LEN
+            }
+            MediaLocator::Arweave { tx_id } => tx_id.len() == ARWEAVE_TX_ID_LEN,
+        }
+    }
+}
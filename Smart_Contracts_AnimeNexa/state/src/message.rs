@@ -0,0 +1,11 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+// Define the Message struct
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Message {
+    pub message_id: String,
+    pub sender_user_id: String,
+    pub recipient_user_id: String,
+    pub message_content: String, // Ideally, this should be encrypted
+    pub timestamp: u64,
+}
@@ -0,0 +1,104 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::manga::AccessGrant;
+
+/// Most contributors a single content's revenue can be split between.
+pub const MAX_REVENUE_SHARES: usize = 8;
+
+// Define the monetized content struct
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct MonetizedContent {
+    pub content_id: String,
+    pub creator_user_id: String,
+    pub price: u64,
+    pub payment_mint: Option<Pubkey>, // None when priced in lamports
+    pub revenue_shares: Vec<RevenueShare>, // Empty when the creator keeps everything
+    pub access_terms: AccessTerms,    // What a purchase grants
+    pub total_sales: u64,
+    pub total_revenue: u64, // Gross amount of all sales, in the payment currency
+}
+
+// Define the purchase struct, stored as one receipt PDA per content and purchaser
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Purchase {
+    pub content: Pubkey, // Monetized content account
+    pub content_id: String,
+    pub purchaser_user_id: String,
+    pub purchaser: Pubkey, // Wallet that paid
+    pub amount: u64,
+    pub payment_mint: Option<Pubkey>,
+    pub timestamp: u64,
+    pub grant: AccessGrant, // Fixed at purchase time from the content's access terms
+    pub platform_fee: u64,
+    pub payouts: Vec<Payout>,
+}
+
+/// Access sold by a piece of content, turned into an [`AccessGrant`] on purchase.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessTerms {
+    Permanent,
+    Rental {
+        duration_seconds: i64,
+    },
+    ChapterRange {
+        first_chapter: u32,
+        last_chapter: u32,
+    },
+}
+
+impl AccessTerms {
+    /// Whether the terms can ever grant access.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            AccessTerms::Permanent => true,
+            AccessTerms::Rental { duration_seconds } => *duration_seconds > 0,
+            AccessTerms::ChapterRange {
+                first_chapter,
+                last_chapter,
+            } => first_chapter <= last_chapter,
+        }
+    }
+
+    /// The grant bought at Unix time `now`, or None if its expiry overflows.
+    pub fn grant_at(&self, now: i64) -> Option<AccessGrant> {
+        Some(match *self {
+            AccessTerms::Permanent => AccessGrant::Permanent,
+            AccessTerms::Rental { duration_seconds } => AccessGrant::Rental {
+                expires_at: now.checked_add(duration_seconds)?,
+            },
+            AccessTerms::ChapterRange {
+                first_chapter,
+                last_chapter,
+            } => AccessGrant::ChapterRange {
+                first_chapter,
+                last_chapter,
+            },
+        })
+    }
+}
+
+// Role of a contributor receiving part of a content's revenue
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContributorRole {
+    Creator,
+    Artist,
+    Writer,
+    Translator,
+    Other,
+}
+
+// Define the revenue share struct, one entry of a content's split table
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct RevenueShare {
+    pub wallet: Pubkey,
+    pub role: ContributorRole,
+    pub basis_points: u16, // Share of the amount left after the platform fee
+}
+
+// Amount paid out to one wallet as part of a purchase
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Payout {
+    pub wallet: Pubkey,
+    pub amount: u64,
+}
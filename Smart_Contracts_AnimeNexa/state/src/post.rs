@@ -0,0 +1,92 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::{media::MediaReference, reaction::ReactionCounts};
+
+/// Most media that can be attached to a single post.
+pub const MAX_POST_MEDIA: usize = 10;
+
+// Limits on the parts of a post's content
+pub const MAX_POST_TEXT_LEN: usize = 2000;
+pub const MAX_POST_MEDIA_REFS: usize = 4;
+pub const MAX_MENTIONS: usize = 10;
+pub const MAX_HASHTAGS: usize = 10;
+pub const MAX_HASHTAG_LEN: usize = 50;
+pub const MAX_CONTENT_WARNINGS: usize = 5;
+
+// Define the post struct
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Post {
+    pub post_id: String,
+    pub user_id: String,
+    pub content: PostContent,
+    pub timestamp: u64,
+    pub media_ids: Vec<String>, // Attached media, in upload order
+    pub revision: u32,          // Number of edits
+    pub previous_content_hash: Option<[u8; 32]>, // SHA-256 of the borsh-encoded content before the last edit
+    pub deleted: bool,                           // Tombstone: content and media have been removed
+    pub comment_count: u32,                      // Comments in the post's thread, replies included
+    pub reactions: ReactionCounts,
+    pub visibility: PostVisibility,
+    pub repost_of: Option<Pubkey>, // Original post account, for reposts and quote posts
+    pub repost_count: u32,         // Users with a live repost of the post
+}
+
+// Define the Repost Record struct: how many live reposts one user has of a
+// post, so the post counts each reposter once
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct RepostRecord {
+    pub user: Pubkey,     // Reposting user's account
+    pub original: Pubkey, // Original post account
+    pub reposts: u32,     // Live reposts and quote posts of the original by the user
+}
+
+/// Who a post is meant for. Account data is public, so this tells clients and
+/// the feed service how to show the post rather than hiding it.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PostVisibility {
+    #[default]
+    Public,
+    // Only shown to users following the author
+    FollowersOnly,
+}
+
+impl PostVisibility {
+    /// The more restricted of the two visibilities.
+    pub fn at_most(self, other: PostVisibility) -> PostVisibility {
+        if self == PostVisibility::FollowersOnly || other == PostVisibility::FollowersOnly {
+            PostVisibility::FollowersOnly
+        } else {
+            PostVisibility::Public
+        }
+    }
+}
+
+// Define the post content struct
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct PostContent {
+    pub text: String,
+    pub media: Vec<MediaReference>, // Embedded media, besides uploaded attachments
+    pub mentions: Vec<String>,      // Usernames, without the leading '@'
+    pub hashtags: Vec<String>,      // Tags, without the leading '#'
+    pub content_warnings: Vec<ContentWarning>, // Readers are warned before the post is shown
+}
+
+/// Reason a post is hidden behind a warning.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContentWarning {
+    Spoiler,
+    Nudity,
+    Violence,
+    FlashingLights,
+    Sensitive,
+}
+
+/// Whether `hashtag` is a valid tag: ASCII letters, digits and underscores only.
+pub fn is_valid_hashtag(hashtag: &str) -> bool {
+    !hashtag.is_empty()
+        && hashtag.len() <= MAX_HASHTAG_LEN
+        && hashtag
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_')
+}
@@ -0,0 +1,60 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+/// Number of [`ReactionKind`] variants, and so of counters per target.
+pub const REACTION_KINDS: usize = 6;
+
+// Define the Reaction struct
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Reaction {
+    pub user_id: String,
+    pub user: Pubkey,   // Reacting user's account
+    pub target: Pubkey, // Post, manga or comment account
+    pub target_kind: ReactionTarget,
+    pub kind: ReactionKind,
+    pub reacted_at: i64, // Unix timestamp from the Clock sysvar
+}
+
+/// Kind of account a reaction is left on.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReactionTarget {
+    Post,
+    Manga,
+    Comment,
+}
+
+/// Reactions a user can leave.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReactionKind {
+    Like,
+    Love,
+    Laugh,
+    Wow,
+    Sad,
+    Fire,
+}
+
+/// Number of reactions of each kind on a target, indexed by [`ReactionKind`].
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReactionCounts {
+    pub counts: [u32; REACTION_KINDS],
+}
+
+impl ReactionCounts {
+    pub fn get(&self, kind: ReactionKind) -> u32 {
+        self.counts[kind as usize]
+    }
+
+    /// Count one more reaction of `kind`, or None on overflow.
+    pub fn add(&mut self, kind: ReactionKind) -> Option<()> {
+        let count = &mut self.counts[kind as usize];
+        *count = count.checked_add(1)?;
+        Some(())
+    }
+
+    /// Count one reaction of `kind` less.
+    pub fn remove(&mut self, kind: ReactionKind) {
+        let count = &mut self.counts[kind as usize];
+        *count = count.saturating_sub(1);
+    }
+}
@@ -0,0 +1,18 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+/// Longest message that can be attached to a tip.
+pub const MAX_SUPPORT_MESSAGE_LEN: usize = 280;
+
+// Define the support struct, recording one tip between users
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct Support {
+    pub support_id: String,
+    pub sender_user_id: String,
+    pub sender: Pubkey, // Sender's user account
+    pub recipient_user_id: String,
+    pub recipient: Pubkey, // Recipient's user account
+    pub amount: u64,       // Lamports
+    pub message: Option<String>,
+    pub timestamp: u64,
+}
@@ -0,0 +1,23 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Define the user struct
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct User {
+    pub user_id: String,
+    pub username: String, // Display form: as registered, without surrounding whitespace
+    pub public_key: Pubkey,
+    pub profile_data: String,
+    pub tips_received_total: u64, // Lifetime lamports received through SupportUser
+    pub tips_received_count: u64,
+    pub follower_count: u64,
+    pub following_count: u64,
+}
+
+// Registry entry reserving a username for a single user account
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct UsernameRecord {
+    pub username: String, // Normalized form used as the PDA seed
+    pub owner: Pubkey,    // Wallet that registered the username
+    pub user_account: Pubkey,
+}
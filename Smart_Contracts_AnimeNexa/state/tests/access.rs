@@ -0,0 +1,125 @@
+//! Access grants bought through monetized content and checked by AccessManga.
+
+use animenexa_state::{AccessGrant, AccessTerms};
+
+#[test]
+fn rental_is_live_until_it_expires() {
+    let grant = AccessGrant::Rental { expires_at: 1_000 };
+    assert!(grant.is_live(999));
+    assert!(!grant.is_live(1_000));
+    assert!(!grant.is_live(1_001));
+}
+
+#[test]
+fn permanent_and_chapter_grants_never_expire() {
+    let range = AccessGrant::ChapterRange {
+        first_chapter: 1,
+        last_chapter: 3,
+    };
+    assert!(AccessGrant::Permanent.is_live(i64::MAX));
+    assert!(range.is_live(i64::MAX));
+}
+
+#[test]
+fn chapter_range_covers_both_ends() {
+    let grant = AccessGrant::ChapterRange {
+        first_chapter: 3,
+        last_chapter: 5,
+    };
+    assert!(!grant.covers_chapter(2, 0));
+    assert!(grant.covers_chapter(3, 0));
+    assert!(grant.covers_chapter(5, 0));
+    assert!(!grant.covers_chapter(6, 0));
+}
+
+#[test]
+fn single_chapter_range_covers_only_that_chapter() {
+    let grant = AccessGrant::ChapterRange {
+        first_chapter: 4,
+        last_chapter: 4,
+    };
+    assert!(grant.covers_chapter(4, 0));
+    assert!(!grant.covers_chapter(3, 0));
+    assert!(!grant.covers_chapter(5, 0));
+}
+
+#[test]
+fn rental_covers_every_chapter_until_it_expires() {
+    let grant = AccessGrant::Rental { expires_at: 1_000 };
+    assert!(grant.covers_chapter(1, 999));
+    assert!(grant.covers_chapter(u32::MAX, 999));
+    assert!(!grant.covers_chapter(1, 1_000));
+}
+
+#[test]
+fn permanent_covers_every_chapter() {
+    assert!(AccessGrant::Permanent.covers_chapter(0, 0));
+    assert!(AccessGrant::Permanent.covers_chapter(u32::MAX, i64::MAX));
+}
+
+#[test]
+fn terms_grant_matching_access() {
+    assert_eq!(
+        AccessTerms::Permanent.grant_at(100),
+        Some(AccessGrant::Permanent)
+    );
+    assert_eq!(
+        AccessTerms::Rental {
+            duration_seconds: 60
+        }
+        .grant_at(100),
+        Some(AccessGrant::Rental { expires_at: 160 })
+    );
+    assert_eq!(
+        AccessTerms::ChapterRange {
+            first_chapter: 1,
+            last_chapter: 2,
+        }
+        .grant_at(100),
+        Some(AccessGrant::ChapterRange {
+            first_chapter: 1,
+            last_chapter: 2,
+        })
+    );
+}
+
+#[test]
+fn rental_expiry_overflow_grants_nothing() {
+    let terms = AccessTerms::Rental {
+        duration_seconds: 2,
+    };
+    assert_eq!(
+        terms.grant_at(i64::MAX - 2),
+        Some(AccessGrant::Rental {
+            expires_at: i64::MAX
+        })
+    );
+    assert_eq!(terms.grant_at(i64::MAX - 1), None);
+}
+
+#[test]
+fn terms_validity() {
+    assert!(AccessTerms::Permanent.is_valid());
+    assert!(AccessTerms::Rental {
+        duration_seconds: 1
+    }
+    .is_valid());
+    assert!(!AccessTerms::Rental {
+        duration_seconds: 0
+    }
+    .is_valid());
+    assert!(!AccessTerms::Rental {
+        duration_seconds: -1
+    }
+    .is_valid());
+    assert!(AccessTerms::ChapterRange {
+        first_chapter: 2,
+        last_chapter: 2,
+    }
+    .is_valid());
+    assert!(!AccessTerms::ChapterRange {
+        first_chapter: 3,
+        last_chapter: 2,
+    }
+    .is_valid());
+}
@@ -0,0 +1,24 @@
+//! Group roles, from most to least privileged.
+
+use animenexa_state::GroupRole;
+
+const ROLES: [GroupRole; 4] = [
+    GroupRole::Owner,
+    GroupRole::Admin,
+    GroupRole::Moderator,
+    GroupRole::Member,
+];
+
+#[test]
+fn roles_outrank_only_less_privileged_roles() {
+    for (i, role) in ROLES.iter().enumerate() {
+        for (j, other) in ROLES.iter().enumerate() {
+            assert_eq!(role.outranks(*other), i < j, "{:?} vs {:?}", role, other);
+        }
+    }
+}
+
+#[test]
+fn no_role_outranks_the_owner() {
+    assert!(ROLES.iter().all(|role| !role.outranks(GroupRole::Owner)));
+}
@@ -0,0 +1,99 @@
+//! MIME types and size and duration limits accepted for each kind of media.
+
+use animenexa_state::{
+    media::{
+        MAX_AUDIO_SIZE, MAX_DURATION_SECONDS, MAX_GIF_SIZE, MAX_IMAGE_SIZE, MAX_MANGA_PAGE_SIZE,
+        MAX_OTHER_SIZE, MAX_VIDEO_SIZE,
+    },
+    MediaType,
+};
+
+const VIDEO: MediaType = MediaType::Video {
+    duration_seconds: 60,
+};
+const AUDIO: MediaType = MediaType::Audio {
+    duration_seconds: 60,
+};
+
+#[test]
+fn images_and_manga_pages_accept_images_but_not_gifs() {
+    for media_type in [MediaType::Image, MediaType::MangaPage] {
+        assert!(media_type.accepts_mime_type("image/png"));
+        assert!(media_type.accepts_mime_type("image/webp"));
+        assert!(!media_type.accepts_mime_type("image/gif"));
+        assert!(!media_type.accepts_mime_type("video/mp4"));
+    }
+}
+
+#[test]
+fn gifs_accept_only_gifs() {
+    assert!(MediaType::Gif.accepts_mime_type("image/gif"));
+    assert!(!MediaType::Gif.accepts_mime_type("image/png"));
+    assert!(!MediaType::Gif.accepts_mime_type("video/gif"));
+}
+
+#[test]
+fn video_and_audio_accept_their_own_kind() {
+    assert!(VIDEO.accepts_mime_type("video/mp4"));
+    assert!(!VIDEO.accepts_mime_type("audio/mpeg"));
+    assert!(AUDIO.accepts_mime_type("audio/mpeg"));
+    assert!(!AUDIO.accepts_mime_type("video/mp4"));
+}
+
+#[test]
+fn other_accepts_anything() {
+    assert!(MediaType::Other.accepts_mime_type("application/pdf"));
+    assert!(MediaType::Other.accepts_mime_type("image/gif"));
+}
+
+#[test]
+fn mime_types_are_case_insensitive() {
+    assert!(MediaType::Image.accepts_mime_type("IMAGE/PNG"));
+    assert!(!MediaType::Image.accepts_mime_type("Image/GIF"));
+    assert!(MediaType::Gif.accepts_mime_type("IMAGE/GIF"));
+    assert!(MediaType::Gif.accepts_mime_type("image/Gif"));
+    assert!(VIDEO.accepts_mime_type("Video/MP4"));
+    assert!(AUDIO.accepts_mime_type("AUDIO/mpeg"));
+}
+
+#[test]
+fn mime_types_without_a_separator_are_only_accepted_as_other() {
+    assert!(!MediaType::Image.accepts_mime_type("image"));
+    assert!(!MediaType::Gif.accepts_mime_type("imagegif"));
+    assert!(!VIDEO.accepts_mime_type("videomp4"));
+    assert!(MediaType::Other.accepts_mime_type("binary"));
+}
+
+#[test]
+fn each_type_has_its_own_size_cap() {
+    let caps = [
+        (MediaType::Image, MAX_IMAGE_SIZE),
+        (MediaType::Gif, MAX_GIF_SIZE),
+        (MediaType::MangaPage, MAX_MANGA_PAGE_SIZE),
+        (VIDEO, MAX_VIDEO_SIZE),
+        (AUDIO, MAX_AUDIO_SIZE),
+        (MediaType::Other, MAX_OTHER_SIZE),
+    ];
+    for (media_type, max_size) in caps {
+        assert!(media_type.within_limits(max_size), "{:?}", media_type);
+        assert!(!media_type.within_limits(max_size + 1), "{:?}", media_type);
+    }
+}
+
+#[test]
+fn gifs_are_capped_below_images() {
+    assert!(MediaType::Image.within_limits(MAX_GIF_SIZE + 1));
+    assert!(!MediaType::Gif.within_limits(MAX_GIF_SIZE + 1));
+}
+
+#[test]
+fn durations_must_be_between_one_second_and_three_hours() {
+    for duration_seconds in [1, MAX_DURATION_SECONDS] {
+        assert!(MediaType::Video { duration_seconds }.within_limits(1));
+        assert!(MediaType::Audio { duration_seconds }.within_limits(1));
+    }
+    for duration_seconds in [0, MAX_DURATION_SECONDS + 1] {
+        assert!(!MediaType::Video { duration_seconds }.within_limits(1));
+        assert!(!MediaType::Audio { duration_seconds }.within_limits(1));
+    }
+}